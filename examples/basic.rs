@@ -11,12 +11,14 @@ fn main() {
     let button = gtk::Button::with_label("Click me!");
     window.add(&button);
 
-    button.stream_label(
-        &button
-            .clicked_events()
-            .scan(0, |a, _| a + 1)
-            .map(|n| format!("clicked {} times", n)),
-    );
+    button
+        .stream_label(
+            &button
+                .clicked_events()
+                .scan(0, |a, _| a + 1)
+                .map(|n| format!("clicked {} times", n)),
+        )
+        .detach();
 
     window
         .delete_events(false)
@@ -1,4 +1,6 @@
+use frappe_gtk::channel::MainThreadSink;
 use frappe_gtk::prelude::*;
+use frappe_gtk::types::ConfirmSpec;
 use gtk::prelude::*;
 use std::thread;
 use std::time::Duration;
@@ -49,47 +51,45 @@ fn main() {
     let counter = cmd_add.clicked_events().scan(0, |a, _| a + 1);
     let counter_str = counter.map(|n| format!("counter: {}", n));
     // do some work on another thread in response to this event
-    let counter_worker = counter_str
-        .map_n(|val, sender| {
-            let val = val.into_owned();
-            thread::spawn(move || {
-                thread::sleep(Duration::from_millis(1000));
-                sender.send(val);
-            });
-        })
-        .to_main_thread();
+    let worker = MainThreadSink::new();
+    let counter_worker = worker.stream();
+    counter_str.observe(move |val| {
+        let (worker, val) = (worker.clone(), val.into_owned());
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(1000));
+            worker.send(val);
+        });
+    });
     // count the pending jobs and use it to display a spinner
     let spinner_active = counter
         .merge_with(&counter_worker, |_| 1, |_| -1)
         .scan(0, |a, n| a + *n)
         .map(|a| *a != 0);
     // update the UI state using the data from the streams
-    lbl_count.stream_label(&counter_str);
-    listbox.stream_add(&counter_worker.map(|s| gtk::Label::new(Some(s.as_str())).show_()));
-    spinner.stream_active(&spinner_active);
+    lbl_count.stream_label(&counter_str).detach();
+    listbox
+        .stream_add(&counter_worker.map(|s| gtk::Label::new(Some(s.as_str())).show_()))
+        .detach();
+    spinner.stream_active(&spinner_active).detach();
 
     // handle the "Remove item" button events
     let del_clicked = cmd_del.clicked_events();
-    let deleted = gtk_lift!(listbox.selected_row)
+    let deleted = listbox
+        .selection_signal()
         .snapshot(&del_clicked, |a, _| a)
         .filter_some();
-    listbox.stream_remove(&deleted);
+    listbox.stream_remove(&deleted).detach();
 
     // handle the window close event
-    let win_ = window.wrap_fragile();
     window
         .delete_events(true)
-        .map(move |_| {
-            gtk::MessageDialog::new(
-                Some(win_.get()),
-                gtk::DialogFlags::MODAL,
-                gtk::MessageType::Warning,
-                gtk::ButtonsType::YesNo,
-                "Are you sure you want to quit?",
-            )
+        .confirm(ConfirmSpec {
+            parent: Some(window.wrap_fragile()),
+            message_type: gtk::MessageType::Warning,
+            text: "Are you sure you want to quit?".to_owned(),
+            ..Default::default()
         })
-        .run_dialog()
-        .filter(|resp| *resp == gtk::ResponseType::Yes.into())
+        .filter(|yes| *yes)
         .observe_strong(|_| gtk::main_quit());
 
     window.show_all();
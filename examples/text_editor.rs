@@ -1,5 +1,6 @@
 use frappe::{Signal, Stream};
 use frappe_gtk::prelude::*;
+use frappe_gtk::types::{DialogClose, FileChooserSpec};
 use gtk::prelude::*;
 use std::path::PathBuf;
 use with_macro::with;
@@ -56,22 +57,28 @@ fn main() {
     let new_ev = mnu_new
         .activate_events()
         .map(|_| gtk::TextView::new().show_());
-    notebook.stream_add(&new_ev);
+    notebook.stream_add(&new_ev).detach();
 
     let buffer = textview.buffer().unwrap();
     let buf_modified = gtk_lift!(buffer.is_modified);
 
-    let _filename = Signal::cyclic(|name| {
-        let new_ev = mnu_new.activate_events();
+    let filename = Signal::cyclic(|name| {
+        let new_ev = mnu_new
+            .activate_events()
+            .merge(&window.shortcut_events("<Control>n").unwrap());
         let name_s1 = confirm_unsaved(&new_ev, &buf_modified, name.clone(), &window);
-        buffer.stream_text(&name_s1.map(|_| String::new()));
-        buffer.stream_modified(&name_s1.map(|_| false));
+        buffer.stream_text(&name_s1.map(|_| String::new())).detach();
+        buffer.stream_modified(&name_s1.map(|_| false)).detach();
 
-        let open_ev = mnu_open.activate_events();
-        let name_s2 = open_file(&open_ev, &buf_modified, name.clone(), &window);
+        let open_ev = mnu_open
+            .activate_events()
+            .merge(&window.shortcut_events("<Control>o").unwrap());
+        let name_s2 = open_file(&open_ev, &buf_modified, name.clone(), &window, &buffer);
 
-        let save_ev = mnu_save.activate_events();
-        let name_s3 = save_file(&save_ev, &name, &window);
+        let save_ev = mnu_save
+            .activate_events()
+            .merge(&window.shortcut_events("<Control>s").unwrap());
+        let name_s3 = save_file(&save_ev, name, &window);
 
         let saveas_ev = mnu_saveas.activate_events();
         let name_s4 = save_file(&saveas_ev, &Default::default(), &window);
@@ -90,6 +97,13 @@ fn main() {
             .hold(None)
     });
 
+    window
+        .stream_title(&filename.map(|name| match name {
+            Some(path) => format!("{} - Text editor", path.display()),
+            None => "Text editor".to_owned(),
+        }))
+        .detach();
+
     window.show_all();
     gtk::main();
 }
@@ -98,7 +112,7 @@ fn main() {
 enum SaveResponse {
     Unmodified,
     DontSave,
-    SaveAs(gtk::FileChooserDialog),
+    SaveAs,
     Save(PathBuf),
 }
 
@@ -109,8 +123,7 @@ fn confirm_unsaved(
     win: &gtk::Window,
 ) -> Stream<Option<PathBuf>> {
     let win_ = win.wrap_fragile();
-    let win2 = win_.clone();
-    modified
+    let responses = modified
         // check if unsaved changes
         .snapshot(trigger, |a, _| a)
         // show save prompt if unsaved
@@ -129,8 +142,8 @@ fn confirm_unsaved(
                 None
             }
         })
-        // run dialog if unsaved (Option<Dialog>)
-        .run_opt_dialog()
+        // run dialog if unsaved (Option<Dialog>), destroy it after the response
+        .map_opt_dialog_with(DialogClose::Destroy, |_, resp| resp)
         // remove cancel (Option<i32>: Some = dialog response, None = unmodified)
         .filter(|optresp| {
             optresp
@@ -144,16 +157,7 @@ fn confirm_unsaved(
                     if let Some(name) = filename.sample() {
                         SaveResponse::Save(name)
                     } else {
-                        let dlg = gtk::FileChooserDialog::with_buttons(
-                            Some("Save file as"),
-                            Some(win2.get()),
-                            gtk::FileChooserAction::Save,
-                            &[
-                                ("_Cancel", gtk::ResponseType::Cancel),
-                                ("_Save", gtk::ResponseType::Accept),
-                            ],
-                        );
-                        SaveResponse::SaveAs(dlg)
+                        SaveResponse::SaveAs
                     }
                 } else if resp == gtk::ResponseType::No {
                     SaveResponse::DontSave
@@ -163,27 +167,16 @@ fn confirm_unsaved(
             } else {
                 SaveResponse::Unmodified
             }
-        })
-        // run the SaveResponse::SaveAs dialog to obtain a filename
-        .map_n(|saveresp, sender| {
-            match saveresp.into_owned() {
-                SaveResponse::SaveAs(dialog) => {
-                    dialog.show();
-                    dialog.connect_response(move |dlg, resp| {
-                        let fname = dlg.filename();
-                        dlg.hide();
-                        if resp == gtk::ResponseType::Accept {
-                            println!("got filename {:?}", fname);
-                            if let Some(name) = fname {
-                                sender.send(SaveResponse::Save(name));
-                            }
-                        }
-                        // cancel gets removed from the stream
-                    });
-                }
-                other => sender.send(other),
-            }
-        })
+        });
+    // run a file chooser on SaveResponse::SaveAs to obtain a filename (cancel gets removed)
+    let saved_as = responses
+        .filter(|saveresp| matches!(saveresp, SaveResponse::SaveAs))
+        .choose_file(save_as_spec(win))
+        .filter_some()
+        .map(|name| SaveResponse::Save(name.into_owned()));
+    responses
+        .filter(|saveresp| !matches!(saveresp, SaveResponse::SaveAs))
+        .merge(&saved_as)
         // save the file
         .map(|saveresp| {
             if let SaveResponse::Save(fname) = saveresp.into_owned() {
@@ -204,9 +197,29 @@ fn open_file(
     modified: &Signal<bool>,
     filename: Signal<Option<PathBuf>>,
     win: &gtk::Window,
+    buffer: &gtk::TextBuffer,
 ) -> Stream<Option<PathBuf>> {
-    eprintln!("open_file: unimplemented");
-    Default::default()
+    let opened = confirm_unsaved(trigger, modified, filename, win)
+        // ask for the file to open (cancel gets removed)
+        .choose_file(FileChooserSpec {
+            title: Some("Open file".to_owned()),
+            parent: Some(win.wrap_fragile()),
+            ..Default::default()
+        })
+        .filter_some()
+        // load the file
+        .filter_map(|name| match std::fs::read_to_string(&*name) {
+            Ok(text) => Some((name.into_owned(), text)),
+            Err(e) => {
+                eprintln!("can't open {:?}: {}", name, e);
+                None
+            }
+        });
+    buffer
+        .stream_text(&opened.map(|file| file.1.clone()))
+        .detach();
+    buffer.stream_modified(&opened.map(|_| false)).detach();
+    opened.map(|file| Some(file.0.clone()))
 }
 
 fn save_file(
@@ -214,39 +227,27 @@ fn save_file(
     filename: &Signal<Option<PathBuf>>,
     win: &gtk::Window,
 ) -> Stream<Option<PathBuf>> {
-    let win_ = win.wrap_fragile();
-    filename
-        .snapshot(trigger, |a, _| a)
-        // obtain the filename from a dialog
-        .map_n(move |optname, sender| {
-            if let Some(name) = optname.into_owned() {
-                sender.send(name);
-            } else {
-                let dialog = gtk::FileChooserDialog::with_buttons(
-                    Some("Save file as"),
-                    Some(win_.get()),
-                    gtk::FileChooserAction::Save,
-                    &[
-                        ("_Cancel", gtk::ResponseType::Cancel),
-                        ("_Save", gtk::ResponseType::Accept),
-                    ],
-                );
-                dialog.show();
-                dialog.connect_response(move |dlg, resp| {
-                    let fname = dlg.filename();
-                    dlg.hide();
-                    if resp == gtk::ResponseType::Accept {
-                        println!("got filename {:?}", fname);
-                        if let Some(name) = fname {
-                            sender.send(name);
-                        }
-                    }
-                });
-            }
-        })
+    let optname = filename.snapshot(trigger, |a, _| a);
+    // obtain the filename from a dialog if we don't have one
+    let chosen = optname
+        .filter(|optname| optname.is_none())
+        .choose_file(save_as_spec(win))
+        .filter_some();
+    optname
+        .filter_some()
+        .merge(&chosen)
         // save the file
         .map(|name| {
             println!("saving file: {:?}", name);
             Some(name.into_owned())
         })
 }
+
+fn save_as_spec(win: &gtk::Window) -> FileChooserSpec {
+    FileChooserSpec {
+        title: Some("Save file as".to_owned()),
+        parent: Some(win.wrap_fragile()),
+        action: gtk::FileChooserAction::Save,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,172 @@
+//! Sending values from other threads into the Gtk main loop.
+
+use frappe::{Sink, Stream};
+use glib::{Continue, MainContext, Priority};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+/// A Sink that can be used from any thread, but emits its values on the main loop.
+///
+/// Clones of this sink can be moved to other threads. The values sent are queued on a
+/// `glib::MainContext::channel` and delivered to the Stream by the thread that runs the context.
+#[derive(Debug)]
+pub struct MainThreadSink<T> {
+    sender: Mutex<glib::Sender<T>>,
+    stream: Stream<T>,
+}
+
+impl<T: Send + 'static> MainThreadSink<T> {
+    /// Creates a new sink attached to the default main context.
+    ///
+    /// Must be called from the main thread.
+    pub fn new() -> Self {
+        Self::with_context(&MainContext::default(), glib::PRIORITY_DEFAULT)
+    }
+
+    /// Creates a new sink attached to the specified main context.
+    ///
+    /// Must be called from the thread that owns `ctx`.
+    pub fn with_context(ctx: &MainContext, priority: Priority) -> Self {
+        let (sender, receiver) = MainContext::channel(priority);
+        let sink = Sink::new();
+        let stream = sink.stream();
+        receiver.attach(Some(ctx), move |val| {
+            sink.send(val);
+            Continue(true)
+        });
+        MainThreadSink {
+            sender: Mutex::new(sender),
+            stream,
+        }
+    }
+
+    /// Sends a value. It will be emitted by the Stream on the next main loop iteration.
+    ///
+    /// Values sent after the main context has been destroyed are discarded.
+    pub fn send(&self, val: T) {
+        let _ = self.sender.lock().unwrap().send(val);
+    }
+
+    /// Sends multiple values.
+    pub fn feed<I: IntoIterator<Item = T>>(&self, iter: I) {
+        let sender = self.sender.lock().unwrap();
+        for val in iter {
+            let _ = sender.send(val);
+        }
+    }
+
+    /// Returns a Stream that receives the values sent to this sink.
+    pub fn stream(&self) -> Stream<T> {
+        self.stream.clone()
+    }
+
+    /// Forwards all the values received from a channel to this sink.
+    ///
+    /// The values are read by a new thread, that ends when the channel is disconnected.
+    pub fn forward(&self, receiver: mpsc::Receiver<T>) -> thread::JoinHandle<()> {
+        let sink = self.clone();
+        thread::spawn(move || {
+            for val in receiver {
+                sink.send(val);
+            }
+        })
+    }
+
+    /// Forwards all the values received from a crossbeam channel to this sink.
+    ///
+    /// The values are read by a new thread, that ends when the channel is disconnected.
+    #[cfg(feature = "crossbeam-channel")]
+    pub fn forward_crossbeam(
+        &self,
+        receiver: crossbeam_channel::Receiver<T>,
+    ) -> thread::JoinHandle<()> {
+        let sink = self.clone();
+        thread::spawn(move || {
+            for val in receiver {
+                sink.send(val);
+            }
+        })
+    }
+}
+
+impl<T: Send + 'static> Default for MainThreadSink<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for MainThreadSink<T> {
+    fn clone(&self) -> Self {
+        MainThreadSink {
+            sender: Mutex::new(self.sender.lock().unwrap().clone()),
+            stream: self.stream.clone(),
+        }
+    }
+}
+
+/// Returns a Stream that emits on the main loop the values received from a channel.
+///
+/// Must be called from the main thread.
+pub fn from_receiver<T: Send + 'static>(receiver: mpsc::Receiver<T>) -> Stream<T> {
+    let sink = MainThreadSink::new();
+    sink.forward(receiver);
+    sink.stream()
+}
+
+/// Returns a Stream that emits on the main loop the values received from a crossbeam channel.
+///
+/// Must be called from the main thread.
+#[cfg(feature = "crossbeam-channel")]
+pub fn from_crossbeam<T: Send + 'static>(receiver: crossbeam_channel::Receiver<T>) -> Stream<T> {
+    let sink = MainThreadSink::new();
+    sink.forward_crossbeam(receiver);
+    sink.stream()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{collect, run_until, with_context};
+    use std::time::Duration;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn send_from_threads() {
+        with_context(|ctx| {
+            let sink = MainThreadSink::with_context(ctx, glib::PRIORITY_DEFAULT);
+            let main_thread = thread::current().id();
+            sink.stream()
+                .observe(move |_| assert_eq!(thread::current().id(), main_thread));
+            let values = collect(&sink.stream());
+            let handles: Vec<_> = (0..4)
+                .map(|i| {
+                    let sink = sink.clone();
+                    thread::spawn(move || sink.send(i))
+                })
+                .collect();
+            handles.into_iter().for_each(|h| h.join().unwrap());
+            assert!(values.lock().unwrap().is_empty());
+            run_until(ctx, TIMEOUT, || values.lock().unwrap().len() == 4);
+            let mut values = values.lock().unwrap().clone();
+            values.sort_unstable();
+            assert_eq!(values, [0, 1, 2, 3]);
+        })
+    }
+
+    #[test]
+    fn forward() {
+        with_context(|ctx| {
+            let sink = MainThreadSink::with_context(ctx, glib::PRIORITY_DEFAULT);
+            let values = collect(&sink.stream());
+            let (tx, rx) = mpsc::channel();
+            let handle = sink.forward(rx);
+            tx.send(1).unwrap();
+            tx.send(2).unwrap();
+            drop(tx);
+            handle.join().unwrap();
+            run_until(ctx, TIMEOUT, || values.lock().unwrap().len() == 2);
+            assert_eq!(*values.lock().unwrap(), [1, 2]);
+        })
+    }
+}
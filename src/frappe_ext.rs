@@ -1,7 +1,24 @@
-use crate::types::Fragile;
-use frappe::Stream;
+use crate::types::{
+    ChoiceSpec, ConfirmSpec, DialogClose, Disconnector, EventStream, FileChooserSpec, Fragile,
+};
+use frappe::{Signal, Sink, Stream};
+use glib::translate::from_glib_full;
+use glib::{MainContext, Priority, SignalHandlerId};
 use gtk::prelude::*;
 use gtk::{ResponseType, Widget};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::mem;
+use std::os::raw::{c_int, c_uint};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Minimum time between two changes sent from a polled Signal (about one frame at 60 Hz).
+const SIGNAL_POLL_INTERVAL: Duration = Duration::from_millis(16);
 
 /// Extension trait for frappe streams.
 pub trait StreamExt<T> {
@@ -9,12 +26,79 @@ pub trait StreamExt<T> {
     fn to_main_thread(&self) -> Stream<T>
     where
         T: Clone + Send + 'static;
+    /// Executes the rest of this stream chain on the thread that runs the specified main context.
+    ///
+    /// Each value is sent from its own idle source with the specified priority.
+    fn to_context(&self, ctx: &MainContext, priority: Priority) -> Stream<T>
+    where
+        T: Clone + Send + 'static;
+    /// Same as `to_context`, but the values are queued and sent together from a single idle
+    /// source, that drains the queue once per main loop iteration.
+    fn to_context_batched(&self, ctx: &MainContext, priority: Priority) -> Stream<T>
+    where
+        T: Clone + Send + 'static;
+    /// Emits the last value received after `period` has passed without receiving new values.
+    ///
+    /// The timers of this and the following operators run on the thread-default main context
+    /// that was active when the operator was created.
+    fn debounce(&self, period: Duration) -> Stream<T>
+    where
+        T: Clone + Send + 'static;
+    /// Emits at most one value per `period`.
+    ///
+    /// The first value is emitted right away, and the last value received during the period is
+    /// emitted when it ends.
+    fn throttle(&self, period: Duration) -> Stream<T>
+    where
+        T: Clone + Send + 'static;
+    /// Emits each value after a delay.
+    fn delay(&self, period: Duration) -> Stream<T>
+    where
+        T: Clone + Send + 'static;
+    /// Emits the last value received every `period`, if there is a new one.
+    fn sample_every(&self, period: Duration) -> Stream<T>
+    where
+        T: Clone + Send + 'static;
+    /// Maps each value into a Future and emits it's result.
+    ///
+    /// The futures run on the thread-default main context that was active when the operator was
+    /// created, so it must be called from the thread that runs that context.
+    fn map_async<F, Fut, R>(&self, f: F) -> Stream<R>
+    where
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = R> + 'static,
+        T: Clone + 'static,
+        R: 'static;
+    /// Same as `map_async`, but drops the running Future when a new value is received.
+    ///
+    /// Only the result of the latest value is emitted.
+    fn switch_map_async<F, Fut, R>(&self, f: F) -> Stream<R>
+    where
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = R> + 'static,
+        T: Clone + 'static,
+        R: 'static;
     /// Wraps each Stream value in a Fragile.
     fn wrap_fragile(&self) -> Stream<Fragile<T>>
     where
         T: Clone + 'static;
     /// Shows a dialog and maps it's response values.
+    ///
+    /// Only the first response of each show is received. Dialogs that arrive while another one
+    /// is being shown are queued until it responds or is destroyed.
+    ///
+    /// The dialog is left on screen after the response, so `f` should hide or destroy it, or the
+    /// next queued dialog is shown on top of it. `map_dialog_with` can do that instead.
     fn map_dialog<F, R>(&self, f: F) -> Stream<R>
+    where
+        F: Fn(&T, ResponseType) -> R + Clone + Send + Sync + 'static,
+        T: IsA<Widget> + DialogExt + 'static,
+        R: 'static,
+    {
+        self.map_dialog_with(DialogClose::Keep, f)
+    }
+    /// Same as `map_dialog`, but also hides or destroys the dialog after the response is mapped.
+    fn map_dialog_with<F, R>(&self, close: DialogClose, f: F) -> Stream<R>
     where
         F: Fn(&T, ResponseType) -> R + Clone + Send + Sync + 'static,
         T: IsA<Widget> + DialogExt + 'static,
@@ -27,11 +111,33 @@ pub trait StreamExt<T> {
     where
         T: IsA<Widget> + DialogExt + 'static,
     {
-        self.map_dialog(|dlg, resp| {
-            dlg.hide();
-            resp
-        })
+        self.map_dialog_with(DialogClose::Hide, |_, resp| resp)
     }
+    /// Shows a file chooser dialog for each value received and returns the chosen file.
+    ///
+    /// Emits `None` if the dialog was cancelled.
+    fn choose_file(&self, spec: FileChooserSpec) -> Stream<Option<PathBuf>>
+    where
+        T: 'static;
+    /// Same as `choose_file`, but allows selecting multiple files.
+    ///
+    /// Emits an empty Vec if the dialog was cancelled.
+    fn choose_files(&self, spec: FileChooserSpec) -> Stream<Vec<PathBuf>>
+    where
+        T: 'static;
+    /// Shows a message dialog for each value received and asks for confirmation.
+    ///
+    /// Emits `true` if the dialog was answered with Yes, Ok or Accept.
+    fn confirm(&self, spec: ConfirmSpec) -> Stream<bool>
+    where
+        T: 'static;
+    /// Shows a message dialog for each value received with a button for each choice.
+    ///
+    /// Emits the selected choice, or `None` if the dialog was closed without choosing.
+    fn ask_choice<E>(&self, spec: ChoiceSpec<E>) -> Stream<Option<E>>
+    where
+        E: Clone + Send + Sync + 'static,
+        T: 'static;
 }
 
 impl<T> StreamExt<T> for Stream<T> {
@@ -39,14 +145,137 @@ impl<T> StreamExt<T> for Stream<T> {
     where
         T: Clone + Send + 'static,
     {
-        self.map_n(|val, sender| {
+        self.to_context(&MainContext::default(), glib::PRIORITY_DEFAULT_IDLE)
+    }
+
+    fn to_context(&self, ctx: &MainContext, priority: Priority) -> Stream<T>
+    where
+        T: Clone + Send + 'static,
+    {
+        let ctx = ctx.clone();
+        self.map_n(move |val, sender| {
+            let mut val = Some(val.into_owned());
+            let source = glib::idle_source_new(None, priority, move || {
+                if let Some(val) = val.take() {
+                    sender.send(val);
+                }
+                Continue(false)
+            });
+            source.attach(Some(&ctx));
+        })
+    }
+
+    fn to_context_batched(&self, ctx: &MainContext, priority: Priority) -> Stream<T>
+    where
+        T: Clone + Send + 'static,
+    {
+        let ctx = ctx.clone();
+        let queue = Arc::new(Mutex::new(Batch {
+            values: Vec::new(),
+            scheduled: false,
+        }));
+        self.map_n(move |val, sender| {
+            let mut batch = queue.lock().unwrap();
+            batch.values.push(val.into_owned());
+            if batch.scheduled {
+                return;
+            }
+            batch.scheduled = true;
+            let queue = queue.clone();
+            let source = glib::idle_source_new(None, priority, move || {
+                let values = {
+                    let mut batch = queue.lock().unwrap();
+                    batch.scheduled = false;
+                    std::mem::take(&mut batch.values)
+                };
+                sender.feed(values);
+                Continue(false)
+            });
+            source.attach(Some(&ctx));
+        })
+    }
+
+    fn debounce(&self, period: Duration) -> Stream<T>
+    where
+        T: Clone + Send + 'static,
+    {
+        let ctx = MainContext::ref_thread_default();
+        let pending = Mutex::new(None::<glib::Source>);
+        self.map_n(move |val, sender| {
             let mut val = Some(val.into_owned());
-            glib::idle_add(move || {
+            let source = attach_timeout(&ctx, period, move || {
                 if let Some(val) = val.take() {
                     sender.send(val);
                 }
                 Continue(false)
             });
+            if let Some(old) = pending.lock().unwrap().replace(source) {
+                old.destroy();
+            }
+        })
+    }
+
+    fn throttle(&self, period: Duration) -> Stream<T>
+    where
+        T: Clone + Send + 'static,
+    {
+        sample_latest(self, period, true)
+    }
+
+    fn delay(&self, period: Duration) -> Stream<T>
+    where
+        T: Clone + Send + 'static,
+    {
+        let ctx = MainContext::ref_thread_default();
+        self.map_n(move |val, sender| {
+            let mut val = Some(val.into_owned());
+            attach_timeout(&ctx, period, move || {
+                if let Some(val) = val.take() {
+                    sender.send(val);
+                }
+                Continue(false)
+            });
+        })
+    }
+
+    fn sample_every(&self, period: Duration) -> Stream<T>
+    where
+        T: Clone + Send + 'static,
+    {
+        sample_latest(self, period, false)
+    }
+
+    fn map_async<F, Fut, R>(&self, f: F) -> Stream<R>
+    where
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = R> + 'static,
+        T: Clone + 'static,
+        R: 'static,
+    {
+        let ctx = MainContext::ref_thread_default();
+        self.map_n(move |val, sender| {
+            let fut = f(val.into_owned());
+            ctx.spawn_local(async move { sender.send(fut.await) });
+        })
+    }
+
+    fn switch_map_async<F, Fut, R>(&self, f: F) -> Stream<R>
+    where
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = R> + 'static,
+        T: Clone + 'static,
+        R: 'static,
+    {
+        let ctx = MainContext::ref_thread_default();
+        let current = Mutex::new(None::<glib::Source>);
+        self.map_n(move |val, sender| {
+            let fut = f(val.into_owned());
+            let id = ctx.spawn_local(async move { sender.send(fut.await) });
+            let source = ctx.find_source_by_id(&id);
+            // destroying the source drops the future, so it won't send it's result
+            if let Some(old) = std::mem::replace(&mut *current.lock().unwrap(), source) {
+                old.destroy();
+            }
         })
     }
 
@@ -57,50 +286,357 @@ impl<T> StreamExt<T> for Stream<T> {
         self.map(|val| Fragile::new(val.into_owned()))
     }
 
-    fn map_dialog<F, R>(&self, f: F) -> Stream<R>
+    fn map_dialog_with<F, R>(&self, close: DialogClose, f: F) -> Stream<R>
     where
         F: Fn(&T, ResponseType) -> R + Clone + Send + Sync + 'static,
-        T: DialogExt + WidgetExt + 'static,
+        T: IsA<Widget> + DialogExt + 'static,
         R: 'static,
     {
+        let queue = DialogQueue::new(close);
         self.map_n(move |dialog, sender| {
-            dialog.show();
             let f = f.clone();
-            dialog.connect_response(move |dlg, resp| sender.send(f(dlg, resp)));
+            DialogQueue::push(
+                queue.get(),
+                dialog.into_owned(),
+                Box::new(move |dlg, resp| sender.send(f(dlg, resp))),
+            );
+        })
+    }
+
+    fn choose_file(&self, spec: FileChooserSpec) -> Stream<Option<PathBuf>>
+    where
+        T: 'static,
+    {
+        self.map(move |_| spec.build(false))
+            .map_dialog_with(DialogClose::Destroy, |dlg, resp| {
+                if resp == ResponseType::Accept {
+                    dlg.filename()
+                } else {
+                    None
+                }
+            })
+    }
+
+    fn choose_files(&self, spec: FileChooserSpec) -> Stream<Vec<PathBuf>>
+    where
+        T: 'static,
+    {
+        self.map(move |_| spec.build(true))
+            .map_dialog_with(DialogClose::Destroy, |dlg, resp| {
+                if resp == ResponseType::Accept {
+                    dlg.filenames()
+                } else {
+                    Vec::new()
+                }
+            })
+    }
+
+    fn confirm(&self, spec: ConfirmSpec) -> Stream<bool>
+    where
+        T: 'static,
+    {
+        self.map(move |_| {
+            message_dialog(
+                spec.parent.as_ref(),
+                spec.message_type,
+                spec.buttons,
+                &spec.text,
+                spec.secondary_text.as_deref(),
+            )
+        })
+        .map_dialog_with(DialogClose::Destroy, |_, resp| {
+            matches!(
+                resp,
+                ResponseType::Yes | ResponseType::Ok | ResponseType::Accept
+            )
+        })
+    }
+
+    fn ask_choice<E>(&self, spec: ChoiceSpec<E>) -> Stream<Option<E>>
+    where
+        E: Clone + Send + Sync + 'static,
+        T: 'static,
+    {
+        let spec = Arc::new(spec);
+        let spec_ = spec.clone();
+        self.map(move |_| {
+            let dialog = message_dialog(
+                spec.parent.as_ref(),
+                spec.message_type,
+                gtk::ButtonsType::None,
+                &spec.text,
+                spec.secondary_text.as_deref(),
+            );
+            for (i, (label, _)) in spec.choices.iter().enumerate() {
+                dialog.add_button(label, ResponseType::Other(i as u16));
+            }
+            dialog
+        })
+        .map_dialog_with(DialogClose::Destroy, move |_, resp| match resp {
+            ResponseType::Other(i) => spec_.choices.get(i as usize).map(|(_, e)| e.clone()),
+            _ => None,
         })
     }
 }
 
+impl FileChooserSpec {
+    /// Creates a file chooser dialog with these options.
+    fn build(&self, select_multiple: bool) -> gtk::FileChooserDialog {
+        use gtk::FileChooserAction::*;
+        let accept = match self.action {
+            Save => "_Save",
+            SelectFolder | CreateFolder => "_Select",
+            _ => "_Open",
+        };
+        let dialog = gtk::FileChooserDialog::with_buttons(
+            self.title.as_deref(),
+            self.parent.as_ref().map(Fragile::get),
+            self.action,
+            &[
+                ("_Cancel", ResponseType::Cancel),
+                (accept, ResponseType::Accept),
+            ],
+        );
+        for spec in &self.filters {
+            let filter = gtk::FileFilter::new();
+            filter.set_name(Some(&spec.name));
+            for mime_type in &spec.mime_types {
+                filter.add_mime_type(mime_type);
+            }
+            for pattern in &spec.patterns {
+                filter.add_pattern(pattern);
+            }
+            dialog.add_filter(&filter);
+        }
+        if let Some(folder) = &self.current_folder {
+            dialog.set_current_folder(folder);
+        }
+        // only the dialogs with a name entry can take a suggested name
+        if let (Some(name), Save | CreateFolder) = (&self.current_name, self.action) {
+            dialog.set_current_name(name);
+        }
+        dialog.set_do_overwrite_confirmation(self.confirm_overwrite);
+        dialog.set_select_multiple(select_multiple);
+        dialog
+    }
+}
+
+/// Creates a modal message dialog.
+fn message_dialog(
+    parent: Option<&Fragile<gtk::Window>>,
+    message_type: gtk::MessageType,
+    buttons: gtk::ButtonsType,
+    text: &str,
+    secondary_text: Option<&str>,
+) -> gtk::MessageDialog {
+    let dialog = gtk::MessageDialog::new(
+        parent.map(Fragile::get),
+        gtk::DialogFlags::MODAL,
+        message_type,
+        buttons,
+        text,
+    );
+    if secondary_text.is_some() {
+        dialog.set_secondary_text(secondary_text);
+    }
+    dialog
+}
+
+type ResponseFn<T> = Box<dyn FnOnce(&T, ResponseType)>;
+
+/// Dialogs waiting to be shown by a dialog runner.
+struct DialogQueue<T> {
+    close: DialogClose,
+    /// If there is a dialog waiting for a response.
+    active: bool,
+    pending: VecDeque<(T, ResponseFn<T>)>,
+}
+
+impl<T: IsA<Widget> + DialogExt + 'static> DialogQueue<T> {
+    fn new(close: DialogClose) -> Fragile<Rc<RefCell<Self>>> {
+        Fragile::new(Rc::new(RefCell::new(DialogQueue {
+            close,
+            active: false,
+            pending: VecDeque::new(),
+        })))
+    }
+
+    /// Shows the dialog now, or after the active one responds.
+    fn push(this: &Rc<RefCell<Self>>, dialog: T, on_response: ResponseFn<T>) {
+        let mut queue = this.borrow_mut();
+        if queue.active {
+            queue.pending.push_back((dialog, on_response));
+        } else {
+            queue.active = true;
+            drop(queue);
+            Self::show(this.clone(), dialog, on_response);
+        }
+    }
+
+    /// Shows the dialog and waits for a single response.
+    ///
+    /// If the dialog is destroyed without responding, the queue moves on to the next one.
+    fn show(this: Rc<RefCell<Self>>, dialog: T, on_response: ResponseFn<T>) {
+        let handlers = Rc::new(Cell::new(None));
+        let handlers_ = handlers.clone();
+        let this_ = this.clone();
+        let on_response = Cell::new(Some(on_response));
+        let response_id = dialog.connect_response(move |dlg, resp| {
+            if !Self::finish(&handlers_, dlg) {
+                return;
+            }
+            if let Some(f) = on_response.take() {
+                f(dlg, resp);
+            }
+            let close = this_.borrow().close;
+            match close {
+                DialogClose::Keep => (),
+                DialogClose::Hide => dlg.hide(),
+                DialogClose::Destroy => unsafe { dlg.destroy() },
+            }
+            Self::show_next(&this_);
+        });
+        let handlers_ = handlers.clone();
+        let destroy_id = dialog.connect_destroy(move |dlg| {
+            if Self::finish(&handlers_, dlg) {
+                Self::show_next(&this);
+            }
+        });
+        handlers.set(Some((response_id, destroy_id)));
+        dialog.show();
+    }
+
+    /// Disconnects the handlers of a shown dialog.
+    ///
+    /// Returns false if the dialog was already finished.
+    fn finish(handlers: &Cell<Option<(SignalHandlerId, SignalHandlerId)>>, dialog: &T) -> bool {
+        match handlers.take() {
+            Some((response_id, destroy_id)) => {
+                dialog.disconnect(response_id);
+                dialog.disconnect(destroy_id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Shows the next pending dialog, or marks the queue as idle.
+    fn show_next(this: &Rc<RefCell<Self>>) {
+        let next = this.borrow_mut().pending.pop_front();
+        match next {
+            Some((dialog, f)) => Self::show(this.clone(), dialog, f),
+            None => this.borrow_mut().active = false,
+        }
+    }
+}
+
+/// Creates a timeout source and attaches it to a main context.
+pub(crate) fn attach_timeout<F>(ctx: &MainContext, period: Duration, f: F) -> glib::Source
+where
+    F: FnMut() -> Continue + Send + 'static,
+{
+    let source = glib::timeout_source_new(period, None, glib::PRIORITY_DEFAULT, f);
+    source.attach(Some(ctx));
+    source
+}
+
+/// Values waiting to be sent by `to_context_batched`.
+struct Batch<T> {
+    values: Vec<T>,
+    /// If there is an idle source that will send the values.
+    scheduled: bool,
+}
+
+/// State of a periodic sampling operator.
+struct Sampler<T> {
+    /// The timer that runs while values are being received.
+    timer: Option<glib::Source>,
+    /// Last value received since the previous tick.
+    latest: Option<T>,
+}
+
+/// Emits the latest value on every period while values are being received.
+///
+/// If `leading` is true, the value that starts the timer is emitted right away.
+fn sample_latest<T>(stream: &Stream<T>, period: Duration, leading: bool) -> Stream<T>
+where
+    T: Clone + Send + 'static,
+{
+    let ctx = MainContext::ref_thread_default();
+    let state = Arc::new(Mutex::new(Sampler {
+        timer: None,
+        latest: None,
+    }));
+    stream.map_n(move |val, sender| {
+        let val = val.into_owned();
+        let mut st = state.lock().unwrap();
+        if st.timer.is_some() {
+            st.latest = Some(val);
+            return;
+        }
+        let state = state.clone();
+        let sender_ = sender.clone();
+        st.timer = Some(attach_timeout(&ctx, period, move || {
+            let mut st = state.lock().unwrap();
+            if let Some(val) = st.latest.take() {
+                drop(st);
+                sender_.send(val);
+                Continue(true)
+            } else {
+                st.timer = None;
+                Continue(false)
+            }
+        }));
+        if leading {
+            drop(st);
+            sender.send(val);
+        } else {
+            st.latest = Some(val);
+        }
+    })
+}
+
 pub trait StreamOptExt<T> {
+    /// Same as `StreamExt::map_dialog`, but `None` values are passed through.
     fn map_opt_dialog<F, R>(&self, f: F) -> Stream<Option<R>>
     where
         F: Fn(&T, ResponseType) -> R + Clone + Send + Sync + 'static,
-        T: DialogExt + WidgetExt + 'static,
+        T: IsA<Widget> + DialogExt + 'static,
+        R: 'static,
+    {
+        self.map_opt_dialog_with(DialogClose::Keep, f)
+    }
+
+    fn map_opt_dialog_with<F, R>(&self, close: DialogClose, f: F) -> Stream<Option<R>>
+    where
+        F: Fn(&T, ResponseType) -> R + Clone + Send + Sync + 'static,
+        T: IsA<Widget> + DialogExt + 'static,
         R: 'static;
 
     fn run_opt_dialog(&self) -> Stream<Option<ResponseType>>
     where
         T: Clone + IsA<Widget> + DialogExt + 'static,
     {
-        self.map_opt_dialog(|dlg, resp| {
-            dlg.hide();
-            resp
-        })
+        self.map_opt_dialog_with(DialogClose::Hide, |_, resp| resp)
     }
 }
 
 impl<T> StreamOptExt<T> for Stream<Option<T>> {
-    fn map_opt_dialog<F, R>(&self, f: F) -> Stream<Option<R>>
+    fn map_opt_dialog_with<F, R>(&self, close: DialogClose, f: F) -> Stream<Option<R>>
     where
         F: Fn(&T, ResponseType) -> R + Clone + Send + Sync + 'static,
-        T: DialogExt + WidgetExt + 'static,
+        T: IsA<Widget> + DialogExt + 'static,
         R: 'static,
     {
+        let queue = DialogQueue::new(close);
         self.map_n(move |optdlg, sender| {
             if let Some(dialog) = optdlg.as_ref() {
-                dialog.show();
                 let f = f.clone();
-                dialog.connect_response(move |dlg, resp| sender.send(Some(f(dlg, resp))));
+                DialogQueue::push(
+                    queue.get(),
+                    dialog.clone(),
+                    Box::new(move |dlg, resp| sender.send(Some(f(dlg, resp)))),
+                );
             } else {
                 sender.send(None)
             }
@@ -108,6 +644,38 @@ impl<T> StreamOptExt<T> for Stream<Option<T>> {
     }
 }
 
+/// Extension trait for `Stream<Result<T, E>>`.
+pub trait StreamResultExt<T, E> {
+    /// Shows an error dialog for each `Err` value, and passes the `Ok` values through.
+    fn show_error(&self, parent: Option<Fragile<gtk::Window>>) -> Stream<T>
+    where
+        T: Clone + 'static,
+        E: fmt::Display + Clone + 'static;
+}
+
+impl<T, E> StreamResultExt<T, E> for Stream<Result<T, E>> {
+    fn show_error(&self, parent: Option<Fragile<gtk::Window>>) -> Stream<T>
+    where
+        T: Clone + 'static,
+        E: fmt::Display + Clone + 'static,
+    {
+        let queue = DialogQueue::new(DialogClose::Destroy);
+        self.map_n(move |result, sender| match result.into_owned() {
+            Ok(val) => sender.send(val),
+            Err(err) => {
+                let dialog = message_dialog(
+                    parent.as_ref(),
+                    gtk::MessageType::Error,
+                    gtk::ButtonsType::Close,
+                    &err.to_string(),
+                    None,
+                );
+                DialogQueue::push(queue.get(), dialog, Box::new(|_, _| ()));
+            }
+        })
+    }
+}
+
 /// Extension trait for `Stream<Fragile<T>>`.
 pub trait StreamFragileExt<T> {
     /// Extracts the inner value from Fragile objects.
@@ -124,3 +692,322 @@ impl<T> StreamFragileExt<T> for Stream<Fragile<T>> {
         self.map(|f| f.into_owned().into_inner())
     }
 }
+
+/// Extension trait for frappe signals.
+pub trait SignalExt<T> {
+    /// Returns a Stream that fires every time the value of this Signal changes.
+    ///
+    /// Signals don't notify their changes, so the value is sampled by the thread-default main
+    /// context every time it's about to wait for events, and a change is sent at most once every
+    /// 16 ms. This doesn't wake up an idle main loop, so a value changed by another thread is only
+    /// seen after the next event. The sampling stops when the Stream is dropped.
+    fn changes(&self) -> EventStream<T>
+    where
+        T: Clone + PartialEq + 'static;
+}
+
+impl<T> SignalExt<T> for Signal<T> {
+    fn changes(&self) -> EventStream<T>
+    where
+        T: Clone + PartialEq + 'static,
+    {
+        let sink = Sink::new();
+        let stream = sink.stream();
+        EventStream::from_disconnector(stream, poll_signal(self, sink))
+    }
+}
+
+/// Sends the value of a Signal into a sink every time it changes.
+///
+/// The Signal is sampled as described in `SignalExt::changes`, until the returned Disconnector is
+/// dropped.
+pub(crate) fn poll_signal<T>(signal: &Signal<T>, sink: Sink<T>) -> Disconnector
+where
+    T: Clone + PartialEq + 'static,
+{
+    let poller = SignalPoller {
+        signal: signal.clone(),
+        sink,
+        last: signal.sample(),
+        pending: None,
+        next_check: None,
+    };
+    let source = attach_poll_source(&MainContext::ref_thread_default(), Box::new(poller));
+    Disconnector::new(move || source.destroy())
+}
+
+/// Result of checking a polled value before the main context waits for events.
+enum PollReady {
+    /// There is a change to send.
+    Ready,
+    /// Nothing to do until the next event.
+    Idle,
+    /// The value can't be checked again yet.
+    Wait(Duration),
+}
+
+/// Value that is checked for changes by a poll source.
+trait PollState {
+    fn prepare(&mut self) -> PollReady;
+    fn dispatch(&mut self);
+}
+
+/// State of `poll_signal`.
+struct SignalPoller<T> {
+    signal: Signal<T>,
+    sink: Sink<T>,
+    last: T,
+    /// Change waiting to be sent.
+    pending: Option<T>,
+    /// Time before which the Signal isn't checked again, after a change is sent.
+    next_check: Option<Instant>,
+}
+
+impl<T: Clone + PartialEq> PollState for SignalPoller<T> {
+    fn prepare(&mut self) -> PollReady {
+        if self.pending.is_some() {
+            return PollReady::Ready;
+        }
+        if let Some(time) = self.next_check {
+            let now = Instant::now();
+            if now < time {
+                return PollReady::Wait(time - now);
+            }
+            self.next_check = None;
+        }
+        let val = self.signal.sample();
+        if self.last == val {
+            return PollReady::Idle;
+        }
+        self.last = val.clone();
+        self.pending = Some(val);
+        PollReady::Ready
+    }
+
+    fn dispatch(&mut self) {
+        if let Some(val) = self.pending.take() {
+            self.next_check = Some(Instant::now() + SIGNAL_POLL_INTERVAL);
+            self.sink.send(val);
+        }
+    }
+}
+
+/// Main context source that runs a `PollState`.
+#[repr(C)]
+struct PollSource {
+    source: glib::ffi::GSource,
+    state: *mut dyn PollState,
+}
+
+static POLL_SOURCE_FUNCS: glib::ffi::GSourceFuncs = glib::ffi::GSourceFuncs {
+    prepare: Some(poll_source_prepare),
+    check: None,
+    dispatch: Some(poll_source_dispatch),
+    finalize: Some(poll_source_finalize),
+    closure_callback: None,
+    closure_marshal: None,
+};
+
+unsafe extern "C" fn poll_source_prepare(
+    source: *mut glib::ffi::GSource,
+    timeout: *mut c_int,
+) -> glib::ffi::gboolean {
+    let state = &mut *(*(source as *mut PollSource)).state;
+    match state.prepare() {
+        PollReady::Ready => {
+            *timeout = 0;
+            glib::ffi::GTRUE
+        }
+        PollReady::Idle => {
+            *timeout = -1;
+            glib::ffi::GFALSE
+        }
+        PollReady::Wait(time) => {
+            // round up, so we don't wake up before it's time
+            *timeout = (time.as_micros() as c_int + 999) / 1000;
+            glib::ffi::GFALSE
+        }
+    }
+}
+
+unsafe extern "C" fn poll_source_dispatch(
+    source: *mut glib::ffi::GSource,
+    _callback: glib::ffi::GSourceFunc,
+    _data: glib::ffi::gpointer,
+) -> glib::ffi::gboolean {
+    (*(*(source as *mut PollSource)).state).dispatch();
+    glib::ffi::G_SOURCE_CONTINUE
+}
+
+unsafe extern "C" fn poll_source_finalize(source: *mut glib::ffi::GSource) {
+    drop(Box::from_raw((*(source as *mut PollSource)).state));
+}
+
+/// Creates a source that runs `state` on every iteration of a main context.
+///
+/// The context must belong to the current thread.
+fn attach_poll_source(ctx: &MainContext, state: Box<dyn PollState>) -> glib::Source {
+    unsafe {
+        let raw = glib::ffi::g_source_new(
+            &POLL_SOURCE_FUNCS as *const _ as *mut _,
+            mem::size_of::<PollSource>() as c_uint,
+        );
+        (*(raw as *mut PollSource)).state = Box::into_raw(state);
+        let source: glib::Source = from_glib_full(raw);
+        source.attach(Some(ctx));
+        source
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{collect, run_for, with_context};
+
+    const PERIOD: Duration = Duration::from_millis(20);
+
+    #[test]
+    fn to_context() {
+        with_context(|ctx| {
+            let sink = Sink::new();
+            let stream = sink.stream().to_context(ctx, glib::PRIORITY_DEFAULT);
+            let values = collect(&stream);
+            let sink_ = sink.clone();
+            std::thread::spawn(move || sink_.feed(vec![1, 2]))
+                .join()
+                .unwrap();
+            assert!(values.lock().unwrap().is_empty());
+            run_for(ctx, PERIOD);
+            assert_eq!(*values.lock().unwrap(), [1, 2]);
+        })
+    }
+
+    #[test]
+    fn to_context_batched() {
+        with_context(|ctx| {
+            let sink = Sink::new();
+            let stream = sink
+                .stream()
+                .to_context_batched(ctx, glib::PRIORITY_DEFAULT);
+            let values = collect(&stream);
+            sink.feed(vec![1, 2, 3]);
+            assert!(values.lock().unwrap().is_empty());
+            // all the values are sent in a single dispatch
+            assert!(ctx.iteration(false));
+            assert_eq!(*values.lock().unwrap(), [1, 2, 3]);
+            assert!(!ctx.pending());
+        })
+    }
+
+    #[test]
+    fn map_async() {
+        with_context(|ctx| {
+            let sink = Sink::new();
+            let stream = sink.stream().map_async(|n: i32| async move { n * 2 });
+            let values = collect(&stream);
+            sink.feed(vec![1, 2]);
+            assert!(values.lock().unwrap().is_empty());
+            run_for(ctx, PERIOD);
+            assert_eq!(*values.lock().unwrap(), [2, 4]);
+        })
+    }
+
+    #[test]
+    fn switch_map_async() {
+        with_context(|ctx| {
+            let sink = Sink::new();
+            let stream = sink.stream().switch_map_async(|n: i32| async move {
+                glib::timeout_future(PERIOD / 4).await;
+                n
+            });
+            let values = collect(&stream);
+            sink.feed(vec![1, 2]);
+            run_for(ctx, PERIOD);
+            assert_eq!(*values.lock().unwrap(), [2]);
+        })
+    }
+
+    #[test]
+    fn debounce() {
+        with_context(|ctx| {
+            let sink = Sink::new();
+            let stream = sink.stream().debounce(PERIOD);
+            let values = collect(&stream);
+            sink.feed(vec![1, 2, 3]);
+            run_for(ctx, PERIOD / 2);
+            assert!(values.lock().unwrap().is_empty());
+            run_for(ctx, PERIOD * 3);
+            assert_eq!(*values.lock().unwrap(), [3]);
+            sink.send(4);
+            run_for(ctx, PERIOD * 3);
+            assert_eq!(*values.lock().unwrap(), [3, 4]);
+        })
+    }
+
+    #[test]
+    fn throttle() {
+        with_context(|ctx| {
+            let sink = Sink::new();
+            let stream = sink.stream().throttle(PERIOD);
+            let values = collect(&stream);
+            sink.feed(vec![1, 2, 3]);
+            assert_eq!(*values.lock().unwrap(), [1]);
+            run_for(ctx, PERIOD * 3);
+            assert_eq!(*values.lock().unwrap(), [1, 3]);
+            sink.send(4);
+            assert_eq!(*values.lock().unwrap(), [1, 3, 4]);
+        })
+    }
+
+    #[test]
+    fn delay() {
+        with_context(|ctx| {
+            let sink = Sink::new();
+            let stream = sink.stream().delay(PERIOD);
+            let values = collect(&stream);
+            sink.feed(vec![1, 2]);
+            assert!(values.lock().unwrap().is_empty());
+            run_for(ctx, PERIOD * 3);
+            assert_eq!(*values.lock().unwrap(), [1, 2]);
+        })
+    }
+
+    #[test]
+    fn sample_every() {
+        with_context(|ctx| {
+            let sink = Sink::new();
+            let stream = sink.stream().sample_every(PERIOD);
+            let values = collect(&stream);
+            sink.feed(vec![1, 2]);
+            assert!(values.lock().unwrap().is_empty());
+            run_for(ctx, PERIOD * 3);
+            assert_eq!(*values.lock().unwrap(), [2]);
+            sink.send(3);
+            run_for(ctx, PERIOD * 3);
+            assert_eq!(*values.lock().unwrap(), [2, 3]);
+        })
+    }
+
+    #[test]
+    fn signal_changes() {
+        with_context(|ctx| {
+            let value = Arc::new(Mutex::new(0));
+            let value_ = value.clone();
+            let signal = Signal::from_fn(move || *value_.lock().unwrap());
+            let changes = signal.changes();
+            let values = collect(&changes);
+            // an unchanged signal doesn't keep the main loop busy
+            assert!(!ctx.pending());
+            *value.lock().unwrap() = 1;
+            assert!(ctx.iteration(false));
+            assert_eq!(*values.lock().unwrap(), [1]);
+            // the next change waits for the poll interval
+            *value.lock().unwrap() = 2;
+            assert!(!ctx.iteration(false));
+            run_for(ctx, PERIOD);
+            assert_eq!(*values.lock().unwrap(), [1, 2]);
+            run_for(ctx, PERIOD);
+            assert!(!ctx.pending());
+        })
+    }
+}
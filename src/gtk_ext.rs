@@ -1,21 +1,64 @@
+use crate::frappe_ext::poll_signal;
 use crate::types::*;
-use frappe::Stream;
+use frappe::{Signal, Sink};
+use glib::value::FromValue;
+use glib::WeakRef;
 use gtk::prelude::*;
 use gtk::{ResponseType, Widget};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Extension trait for `gtk::Adjustment`.
+pub trait FrpAdjustmentExt {
+    fn stream_value(&self, stream: impl IntoBinding<f64>) -> Subscription;
+    /// Sets the limits and step. The value is clamped to the new limits.
+    fn stream_bounds(&self, stream: impl IntoBinding<ValueBounds>) -> Subscription;
+    /// Returns a Stream with the value every time it changes.
+    fn value_changed_events(&self) -> EventStream<f64>;
+}
+
+impl<T> FrpAdjustmentExt for T
+where
+    T: IsA<gtk::Adjustment>,
+{
+    fn stream_value(&self, stream: impl IntoBinding<f64>) -> Subscription {
+        gtk_observe!(stream, |v| self.set_value(*v))
+    }
+
+    fn stream_bounds(&self, stream: impl IntoBinding<ValueBounds>) -> Subscription {
+        bind_object(self, stream, |adj, bounds| set_bounds(adj.as_ref(), bounds))
+    }
+
+    fn value_changed_events(&self) -> EventStream<f64> {
+        connect_stream!(self.connect_value_changed, |adj| adj.value())
+    }
+}
 
 /// Extension trait for `gtk::BoxExt`.
 pub trait FrpBoxExt {
     /// Appends widgets received from a Stream.
-    fn stream_pack_start<W: IsA<Widget> + 'static>(&self, stream: &Stream<PackArgs<W>>);
+    fn stream_pack_start<W: IsA<Widget> + 'static>(
+        &self,
+        stream: impl IntoBinding<PackArgs<W>>,
+    ) -> Subscription;
     /// Prepends widgets received from a Stream.
-    fn stream_pack_end<W: IsA<Widget> + 'static>(&self, stream: &Stream<PackArgs<W>>);
+    fn stream_pack_end<W: IsA<Widget> + 'static>(
+        &self,
+        stream: impl IntoBinding<PackArgs<W>>,
+    ) -> Subscription;
 }
 
 impl<T> FrpBoxExt for T
 where
     T: BoxExt + ObjectExt + 'static,
 {
-    fn stream_pack_start<W: IsA<Widget> + 'static>(&self, stream: &Stream<PackArgs<W>>) {
+    fn stream_pack_start<W: IsA<Widget> + 'static>(
+        &self,
+        stream: impl IntoBinding<PackArgs<W>>,
+    ) -> Subscription {
         gtk_observe!(stream, |args| self.pack_start(
             &args.child,
             args.expand,
@@ -24,7 +67,10 @@ where
         ))
     }
 
-    fn stream_pack_end<W: IsA<Widget> + 'static>(&self, stream: &Stream<PackArgs<W>>) {
+    fn stream_pack_end<W: IsA<Widget> + 'static>(
+        &self,
+        stream: impl IntoBinding<PackArgs<W>>,
+    ) -> Subscription {
         gtk_observe!(stream, |args| self.pack_end(
             &args.child,
             args.expand,
@@ -37,34 +83,47 @@ where
 /// Extension trait for `gtk::ButtonExt`.
 pub trait FrpButtonExt {
     /// Sets the label with the values from a Stream.
-    fn stream_label(&self, stream: &Stream<String>);
+    fn stream_label(&self, stream: impl IntoBinding<String>) -> Subscription;
     /// Returns a `clicked` event Stream.
-    fn clicked_events(&self) -> Stream<()>;
+    fn clicked_events(&self) -> EventStream<()>;
 }
 
 impl<T> FrpButtonExt for T
 where
     T: ButtonExt + ObjectExt + 'static,
 {
-    fn stream_label(&self, stream: &Stream<String>) {
+    fn stream_label(&self, stream: impl IntoBinding<String>) -> Subscription {
         gtk_observe!(stream, |s| self.set_label(&s))
     }
 
-    fn clicked_events(&self) -> Stream<()> {
+    fn clicked_events(&self) -> EventStream<()> {
         connect_stream!(self.connect_clicked)
     }
 }
 
 /// Extension trait for gtk CheckMenuItem
-trait FrpCheckMenuItemExt {
-    fn toggled_events(&self) -> Stream<ToggleState>;
+pub trait FrpCheckMenuItemExt {
+    fn stream_active(&self, stream: impl IntoBinding<bool>) -> Subscription;
+    fn stream_toggle_state(&self, stream: impl IntoBinding<ToggleState>) -> Subscription;
+    fn toggled_events(&self) -> EventStream<ToggleState>;
 }
 
 impl<T> FrpCheckMenuItemExt for T
 where
-    T: CheckMenuItemExt,
+    T: IsA<gtk::CheckMenuItem>,
 {
-    fn toggled_events(&self) -> Stream<ToggleState> {
+    fn stream_active(&self, stream: impl IntoBinding<bool>) -> Subscription {
+        gtk_observe!(stream, |b| self.set_active(*b))
+    }
+
+    fn stream_toggle_state(&self, stream: impl IntoBinding<ToggleState>) -> Subscription {
+        bind_object(self, stream, |item, state| {
+            item.set_inconsistent(state.inconsistent);
+            item.set_active(state.active);
+        })
+    }
+
+    fn toggled_events(&self) -> EventStream<ToggleState> {
         connect_stream!(self.connect_toggled, |this| ToggleState {
             active: this.is_active(),
             inconsistent: this.is_inconsistent(),
@@ -72,35 +131,79 @@ where
     }
 }
 
+/// Extension trait for `gtk::ComboBoxExt`.
+pub trait FrpComboBoxExt {
+    /// Sets the active item by it's id. `None` or an unknown id unsets the active item.
+    fn stream_active_id(&self, stream: impl IntoBinding<Option<String>>) -> Subscription;
+    /// Returns a Stream with the id of the active item every time it changes.
+    fn active_id_events(&self) -> EventStream<Option<String>>;
+}
+
+impl<T> FrpComboBoxExt for T
+where
+    T: ComboBoxExt + ObjectExt + 'static,
+{
+    fn stream_active_id(&self, stream: impl IntoBinding<Option<String>>) -> Subscription {
+        gtk_observe!(stream, |id| self.set_active_id(id.as_deref()))
+    }
+
+    fn active_id_events(&self) -> EventStream<Option<String>> {
+        connect_stream!(self.connect_changed, |cb| cb.active_id().map(Into::into))
+    }
+}
+
+/// Extension trait for `gtk::ComboBoxText`.
+pub trait FrpComboBoxTextExt {
+    /// Replaces the items with `(id, label)` pairs from a Stream or Signal.
+    ///
+    /// Items are matched to the existing rows by id, so rows are only inserted, moved, relabeled
+    /// or removed when they change. The active item is kept if it's id is still present, and
+    /// `changed` is only emitted when it isn't.
+    fn bind_items(&self, items: impl IntoBinding<Vec<(String, String)>>) -> Subscription;
+}
+
+impl<T> FrpComboBoxTextExt for T
+where
+    T: IsA<gtk::ComboBoxText>,
+{
+    fn bind_items(&self, items: impl IntoBinding<Vec<(String, String)>>) -> Subscription {
+        bind_object(
+            self.upcast_ref::<gtk::ComboBoxText>(),
+            items,
+            |combo, items| update_combo_items(combo, items),
+        )
+    }
+}
+
 /// Extension trait for `gtk::ContainerExt`.
 pub trait FrpContainerExt {
     /// Adds the widgets received from a Stream.
-    fn stream_add<W: IsA<Widget> + 'static>(&self, stream: &Stream<W>);
+    fn stream_add<W: IsA<Widget> + 'static>(&self, stream: impl IntoBinding<W>) -> Subscription;
     /// Removes the widgets received from a Stream.
-    fn stream_remove<W: IsA<Widget> + 'static>(&self, stream: &Stream<W>);
+    fn stream_remove<W: IsA<Widget> + 'static>(&self, stream: impl IntoBinding<W>) -> Subscription;
     /// Returns an `add` event Stream.
-    fn add_events(&self) -> Stream<Widget>;
+    fn add_events(&self) -> EventStream<Widget>;
     /// Returns a `remove` event Stream.
-    fn remove_events(&self) -> Stream<Widget>;
+    fn remove_events(&self) -> EventStream<Widget>;
 }
 
 impl<T> FrpContainerExt for T
 where
     T: ContainerExt + ObjectExt + 'static,
 {
-    fn stream_add<W: IsA<Widget> + 'static>(&self, stream: &Stream<W>) {
+    fn stream_add<W: IsA<Widget> + 'static>(&self, stream: impl IntoBinding<W>) -> Subscription {
         gtk_observe!(stream, |widget| self.add(widget.as_ref()))
     }
 
-    fn stream_remove<W: IsA<Widget> + 'static>(&self, stream: &Stream<W>) {
+    fn stream_remove<W: IsA<Widget> + 'static>(&self, stream: impl IntoBinding<W>) -> Subscription {
         gtk_observe!(stream, |widget| self.remove(widget.as_ref()))
     }
 
-    fn add_events(&self) -> Stream<Widget> {
+    fn add_events(&self) -> EventStream<Widget> {
         connect_stream!(self.connect_add, |_, widget| widget)
     }
 
-    fn remove_events(&self) -> Stream<Widget> {
+    fn remove_events(&self) -> EventStream<Widget> {
         connect_stream!(self.connect_remove, |_, widget| widget)
     }
 }
@@ -108,51 +211,144 @@ where
 /// Extension trait for `gtk::DialogExt`.
 pub trait FrpDialogExt {
     /// Returns a `close` event Stream.
-    fn close_events(&self) -> Stream<()>;
+    fn close_events(&self) -> EventStream<()>;
     /// Returns a `response` event Stream.
-    fn response_events(&self) -> Stream<ResponseType>;
+    fn response_events(&self) -> EventStream<ResponseType>;
 }
 
 impl<T> FrpDialogExt for T
 where
-    T: DialogExt,
+    T: DialogExt + ObjectExt,
 {
-    fn close_events(&self) -> Stream<()> {
+    fn close_events(&self) -> EventStream<()> {
         connect_stream!(self.connect_close)
     }
 
-    fn response_events(&self) -> Stream<ResponseType> {
+    fn response_events(&self) -> EventStream<ResponseType> {
         connect_stream!(self.connect_response, |_, id| id)
     }
 }
 
 /// Extension trait for `gtk::EntryExt`
 pub trait FrpEntryExt {
-    fn stream_text(&self, stream: &Stream<String>);
-    fn activate_events(&self) -> Stream<()>;
-    fn backspace_events(&self) -> Stream<()>;
+    fn stream_text(&self, stream: impl IntoBinding<String>) -> Subscription;
+    fn activate_events(&self) -> EventStream<()>;
+    fn backspace_events(&self) -> EventStream<()>;
+    /// Returns a Stream with the text every time it changes.
+    fn changed_events(&self) -> EventStream<String>;
+    /// Returns a Stream with the text inserted by the user or the program.
+    fn insert_text_events(&self) -> EventStream<TextInsert>;
+    /// Same as `insert_text_events`, but passes the inserted text through a filter first.
+    ///
+    /// The filter can return the same text to accept it, a different text to insert instead, or
+    /// `None` to cancel the insertion. Cancelled insertions are not sent to the Stream.
+    fn insert_text_events_with<F>(&self, filter: F) -> EventStream<TextInsert>
+    where
+        F: Fn(&str) -> Option<String> + 'static;
+    /// Returns a Stream with the character ranges deleted.
+    fn delete_text_events(&self) -> EventStream<TextDelete>;
+    /// Returns a Stream that fires when the icon in the specified position is pressed.
+    fn icon_press_events(&self, icon_pos: gtk::EntryIconPosition) -> EventStream<()>;
+    /// Returns a Signal with the current text.
+    fn text_signal(&self) -> Signal<String>;
 }
 
 impl<T> FrpEntryExt for T
 where
-    T: EntryExt + ObjectExt + 'static,
+    T: IsA<gtk::Entry> + IsA<gtk::Editable>,
 {
-    fn stream_text(&self, stream: &Stream<String>) {
+    fn stream_text(&self, stream: impl IntoBinding<String>) -> Subscription {
         gtk_observe!(stream, |s| self.set_text(&s))
     }
 
-    fn activate_events(&self) -> Stream<()> {
+    fn activate_events(&self) -> EventStream<()> {
         connect_stream!(self.connect_activate)
     }
 
-    fn backspace_events(&self) -> Stream<()> {
+    fn backspace_events(&self) -> EventStream<()> {
         connect_stream!(self.connect_backspace)
     }
+
+    fn changed_events(&self) -> EventStream<String> {
+        connect_stream!(self.connect_changed, |entry| entry.text().to_string())
+    }
+
+    fn insert_text_events(&self) -> EventStream<TextInsert> {
+        connect_stream!(self.connect_insert_text, |_, text, pos| TextInsert {
+            text: text.to_owned(),
+            position: *pos,
+        })
+    }
+
+    fn insert_text_events_with<F>(&self, filter: F) -> EventStream<TextInsert>
+    where
+        F: Fn(&str) -> Option<String> + 'static,
+    {
+        let sink = Sink::new();
+        let stream = sink.stream();
+        // set while we insert the replacement text, so we don't filter it again
+        let inserting = Cell::new(false);
+        let id = self.connect_insert_text(move |entry, text, pos| {
+            if inserting.get() {
+                return;
+            }
+            let position = *pos;
+            match filter(text) {
+                Some(new_text) if new_text == text => (),
+                Some(new_text) => {
+                    inserting.set(true);
+                    entry.insert_text(&new_text, pos);
+                    inserting.set(false);
+                    entry.stop_signal_emission_by_name("insert-text");
+                    sink.send(TextInsert {
+                        text: new_text,
+                        position,
+                    });
+                    return;
+                }
+                None => {
+                    entry.stop_signal_emission_by_name("insert-text");
+                    return;
+                }
+            }
+            sink.send(TextInsert {
+                text: text.to_owned(),
+                position,
+            })
+        });
+        EventStream::new(stream, self, id)
+    }
+
+    fn delete_text_events(&self) -> EventStream<TextDelete> {
+        connect_stream!(self.connect_delete_text, |_, start, end| TextDelete {
+            start,
+            end
+        })
+    }
+
+    fn icon_press_events(&self, icon_pos: gtk::EntryIconPosition) -> EventStream<()> {
+        let sink = Sink::new();
+        let stream = sink.stream();
+        let id = self.connect_icon_press(move |_, pos, _| {
+            if pos == icon_pos {
+                sink.send(())
+            }
+        });
+        EventStream::new(stream, self, id)
+    }
+
+    fn text_signal(&self) -> Signal<String> {
+        cached_signal(
+            self,
+            |entry, dirty| entry.connect_changed(move |_| dirty()),
+            |entry| entry.text().to_string(),
+        )
+    }
 }
 
 /// Extension trait for `gtk::ExpanderExt`.
 pub trait FrpExpanderExt {
-    fn stream_expanded(&self, stream: &Stream<bool>);
+    fn stream_expanded(&self, stream: impl IntoBinding<bool>) -> Subscription;
 }
 
 impl<T> FrpExpanderExt for T
@@ -160,21 +356,59 @@ where
     T: ExpanderExt + ObjectExt + 'static,
 {
     /// Sets the expanded property using the values from a Stream.
-    fn stream_expanded(&self, stream: &Stream<bool>) {
+    fn stream_expanded(&self, stream: impl IntoBinding<bool>) -> Subscription {
         gtk_observe!(stream, |b| self.set_expanded(*b))
     }
 }
 
+/// Extension trait for `gtk::FlowBox`.
+pub trait FrpFlowBoxExt {
+    /// Returns a Stream with the activated children.
+    fn child_activated_events(&self) -> EventStream<gtk::FlowBoxChild>;
+    /// Returns a Stream with the selected children every time the selection changes.
+    fn selected_children_events(&self) -> EventStream<Vec<gtk::FlowBoxChild>>;
+    /// Returns a Signal with the selected children.
+    fn selection_signal(&self) -> Signal<Vec<gtk::FlowBoxChild>>;
+}
+
+impl<T> FrpFlowBoxExt for T
+where
+    T: IsA<gtk::FlowBox>,
+{
+    fn child_activated_events(&self) -> EventStream<gtk::FlowBoxChild> {
+        connect_stream!(self.connect_child_activated, |_, child| child.clone())
+    }
+
+    fn selected_children_events(&self) -> EventStream<Vec<gtk::FlowBoxChild>> {
+        connect_stream!(self.connect_selected_children_changed, |fb| fb
+            .selected_children())
+    }
+
+    fn selection_signal(&self) -> Signal<Vec<gtk::FlowBoxChild>> {
+        cached_signal(
+            self,
+            |fb, dirty| fb.connect_selected_children_changed(move |_| dirty()),
+            |fb| fb.selected_children(),
+        )
+    }
+}
+
 /// Extension trait for `gtk::GridExt`.
 pub trait FrpGridExt {
-    fn stream_attach<W: IsA<Widget> + 'static>(&self, stream: &Stream<GridArgs<W>>);
+    fn stream_attach<W: IsA<Widget> + 'static>(
+        &self,
+        stream: impl IntoBinding<GridArgs<W>>,
+    ) -> Subscription;
 }
 
 impl<T> FrpGridExt for T
 where
     T: GridExt + ObjectExt + 'static,
 {
-    fn stream_attach<W: IsA<Widget> + 'static>(&self, stream: &Stream<GridArgs<W>>) {
+    fn stream_attach<W: IsA<Widget> + 'static>(
+        &self,
+        stream: impl IntoBinding<GridArgs<W>>,
+    ) -> Subscription {
         gtk_observe!(stream, |args| self.attach(
             &args.child,
             args.left,
@@ -187,114 +421,230 @@ where
 
 /// Extension trait for `gtk::GtkWindowExt`.
 pub trait FrpGtkWindowExt {
-    fn stream_position(&self, stream: &Stream<gtk::WindowPosition>);
-    fn stream_size(&self, stream: &Stream<(i32, i32)>);
-    fn stream_title(&self, stream: &Stream<String>);
-    fn activate_default_events(&self) -> Stream<()>;
-    fn activate_focus_events(&self) -> Stream<()>;
+    fn stream_position(&self, stream: impl IntoBinding<gtk::WindowPosition>) -> Subscription;
+    fn stream_size(&self, stream: impl IntoBinding<(i32, i32)>) -> Subscription;
+    fn stream_title(&self, stream: impl IntoBinding<String>) -> Subscription;
+    fn activate_default_events(&self) -> EventStream<()>;
+    fn activate_focus_events(&self) -> EventStream<()>;
 }
 
 impl<T> FrpGtkWindowExt for T
 where
     T: GtkWindowExt + ObjectExt + 'static,
 {
-    fn stream_position(&self, stream: &Stream<gtk::WindowPosition>) {
+    fn stream_position(&self, stream: impl IntoBinding<gtk::WindowPosition>) -> Subscription {
         gtk_observe!(stream, |pos| self.set_position(*pos))
     }
 
-    fn stream_size(&self, stream: &Stream<(i32, i32)>) {
+    fn stream_size(&self, stream: impl IntoBinding<(i32, i32)>) -> Subscription {
         gtk_observe!(stream, |args| self.resize(args.0, args.1))
     }
 
-    fn stream_title(&self, stream: &Stream<String>) {
+    fn stream_title(&self, stream: impl IntoBinding<String>) -> Subscription {
         gtk_observe!(stream, |s| self.set_title(&s))
     }
 
-    fn activate_default_events(&self) -> Stream<()> {
+    fn activate_default_events(&self) -> EventStream<()> {
         connect_stream!(self.connect_activate_default)
     }
 
-    fn activate_focus_events(&self) -> Stream<()> {
+    fn activate_focus_events(&self) -> EventStream<()> {
         connect_stream!(self.connect_activate_focus)
     }
 }
 
 /// Extension trait for `gtk::LabelExt`.
 pub trait FrpLabelExt {
-    fn stream_label(&self, stream: &Stream<String>);
+    fn stream_label(&self, stream: impl IntoBinding<String>) -> Subscription;
 }
 
 impl<T> FrpLabelExt for T
 where
     T: LabelExt + ObjectExt + 'static,
 {
-    fn stream_label(&self, stream: &Stream<String>) {
+    fn stream_label(&self, stream: impl IntoBinding<String>) -> Subscription {
         gtk_observe!(stream, |s| self.set_label(&s))
     }
 }
 
 /// Extension trait for `gtk::LinkButtonExt`.
 pub trait FrpLinkButtonExt {
-    fn activate_link_events(&self, inhibit: bool) -> Stream<()>;
+    fn activate_link_events(&self, inhibit: bool) -> EventStream<()>;
+    /// Like `activate_link_events`, but the propagation of each event is decided by `inhibit`.
+    fn activate_link_events_with<F>(&self, inhibit: F) -> EventStream<()>
+    where
+        F: Fn() -> bool + 'static;
 }
 
 impl<T> FrpLinkButtonExt for T
 where
-    T: LinkButtonExt,
+    T: LinkButtonExt + ObjectExt,
 {
-    fn activate_link_events(&self, inhibit: bool) -> Stream<()> {
-        connect_stream!(self.connect_activate_link, |_| (); Inhibit(inhibit))
+    fn activate_link_events(&self, inhibit: bool) -> EventStream<()> {
+        self.activate_link_events_with(move || inhibit)
+    }
+
+    fn activate_link_events_with<F>(&self, inhibit: F) -> EventStream<()>
+    where
+        F: Fn() -> bool + 'static,
+    {
+        connect_stream!(self.connect_activate_link, |_| () => move |_: &()| inhibit())
+    }
+}
+
+/// Extension trait for `gtk::ListBox`.
+pub trait FrpListBoxExt {
+    /// Keeps the rows of this ListBox in sync with a list of items.
+    ///
+    /// Items are matched to rows by the key returned by `key_fn`. New items get a row created by
+    /// `build_row`, changed items are passed to `update_row`, and rows are only moved or removed
+    /// when their item is. Moved rows keep their selection and focus.
+    ///
+    /// The rows are ordered with a sort function, which replaces any that was set on the ListBox.
+    fn bind_items<V, K, W, FK, FB, FU>(
+        &self,
+        items: impl IntoBinding<Vec<V>>,
+        key_fn: FK,
+        build_row: FB,
+        update_row: FU,
+    ) -> Subscription
+    where
+        V: Clone + PartialEq + 'static,
+        K: Eq + Hash + 'static,
+        W: IsA<Widget>,
+        FK: Fn(&V) -> K + 'static,
+        FB: Fn(&V) -> W + 'static,
+        FU: Fn(&W, &V) + 'static;
+    /// Returns a Stream with the selected row every time it changes.
+    fn row_selected_events(&self) -> EventStream<Option<gtk::ListBoxRow>>;
+    /// Returns a Stream with the activated rows.
+    fn row_activated_events(&self) -> EventStream<gtk::ListBoxRow>;
+    /// Returns a Stream with the selected rows every time the selection changes.
+    fn selected_rows_events(&self) -> EventStream<Vec<gtk::ListBoxRow>>;
+    /// Returns a Signal with the selected row.
+    fn selection_signal(&self) -> Signal<Option<gtk::ListBoxRow>>;
+    /// Returns a Signal with the selected rows, for ListBoxes that allow multiple selection.
+    fn selected_rows_signal(&self) -> Signal<Vec<gtk::ListBoxRow>>;
+}
+
+impl<T> FrpListBoxExt for T
+where
+    T: IsA<gtk::ListBox>,
+{
+    fn bind_items<V, K, W, FK, FB, FU>(
+        &self,
+        items: impl IntoBinding<Vec<V>>,
+        key_fn: FK,
+        build_row: FB,
+        update_row: FU,
+    ) -> Subscription
+    where
+        V: Clone + PartialEq + 'static,
+        K: Eq + Hash + 'static,
+        W: IsA<Widget>,
+        FK: Fn(&V) -> K + 'static,
+        FB: Fn(&V) -> W + 'static,
+        FU: Fn(&W, &V) + 'static,
+    {
+        let order = Rc::new(RefCell::new(HashMap::new()));
+        let order_ = order.clone();
+        self.set_sort_func(Some(Box::new(move |a, b| {
+            let order = order_.borrow();
+            // new rows go last until they get a position
+            let pos = |row| order.get(row).copied().unwrap_or(usize::MAX);
+            pos(a).cmp(&pos(b)) as i32
+        })));
+        let rows = RefCell::new(ListRows {
+            rows: Vec::new(),
+            order,
+            key_fn: Box::new(key_fn),
+            build_row: Box::new(build_row),
+            update_row: Box::new(update_row),
+        });
+        bind_object(
+            self.upcast_ref::<gtk::ListBox>(),
+            items,
+            move |listbox, items| rows.borrow_mut().update(listbox, items),
+        )
+    }
+
+    fn row_selected_events(&self) -> EventStream<Option<gtk::ListBoxRow>> {
+        connect_stream!(self.connect_row_selected, |_, row| row.cloned())
+    }
+
+    fn row_activated_events(&self) -> EventStream<gtk::ListBoxRow> {
+        connect_stream!(self.connect_row_activated, |_, row| row.clone())
+    }
+
+    fn selected_rows_events(&self) -> EventStream<Vec<gtk::ListBoxRow>> {
+        connect_stream!(self.connect_selected_rows_changed, |lb| lb.selected_rows())
+    }
+
+    fn selection_signal(&self) -> Signal<Option<gtk::ListBoxRow>> {
+        cached_signal(
+            self,
+            |lb, dirty| lb.connect_selected_rows_changed(move |_| dirty()),
+            |lb| lb.selected_row(),
+        )
+    }
+
+    fn selected_rows_signal(&self) -> Signal<Vec<gtk::ListBoxRow>> {
+        cached_signal(
+            self,
+            |lb, dirty| lb.connect_selected_rows_changed(move |_| dirty()),
+            |lb| lb.selected_rows(),
+        )
     }
 }
 
 /// Extension trait for `gtk::MenuItemExt`.
 pub trait FrpMenuItemExt {
-    fn activate_events(&self) -> Stream<()>;
+    fn activate_events(&self) -> EventStream<()>;
 }
 
 impl<T> FrpMenuItemExt for T
 where
-    T: GtkMenuItemExt,
+    T: GtkMenuItemExt + ObjectExt,
 {
-    fn activate_events(&self) -> Stream<()> {
+    fn activate_events(&self) -> EventStream<()> {
         connect_stream!(self.connect_activate)
     }
 }
 
 /// Extension trait for `gtk::NotebookExt`.
 pub trait FrpNotebookExt {
-    fn page_added_events(&self) -> Stream<NotebookPage>;
-    fn page_removed_events(&self) -> Stream<NotebookPage>;
-    fn page_reordered_events(&self) -> Stream<NotebookPage>;
-    fn switch_page_events(&self) -> Stream<NotebookPage>;
+    fn page_added_events(&self) -> EventStream<NotebookPage>;
+    fn page_removed_events(&self) -> EventStream<NotebookPage>;
+    fn page_reordered_events(&self) -> EventStream<NotebookPage>;
+    fn switch_page_events(&self) -> EventStream<NotebookPage>;
 }
 
 impl<T> FrpNotebookExt for T
 where
-    T: NotebookExt,
+    T: NotebookExt + ObjectExt,
 {
-    fn page_added_events(&self) -> Stream<NotebookPage> {
+    fn page_added_events(&self) -> EventStream<NotebookPage> {
         connect_stream!(self.connect_page_added, |_, w, page_num| NotebookPage {
             child: w.clone(),
             page_num,
         })
     }
 
-    fn page_removed_events(&self) -> Stream<NotebookPage> {
+    fn page_removed_events(&self) -> EventStream<NotebookPage> {
         connect_stream!(self.connect_page_removed, |_, w, page_num| NotebookPage {
             child: w.clone(),
             page_num,
         })
     }
 
-    fn page_reordered_events(&self) -> Stream<NotebookPage> {
+    fn page_reordered_events(&self) -> EventStream<NotebookPage> {
         connect_stream!(self.connect_page_reordered, |_, w, page_num| NotebookPage {
             child: w.clone(),
             page_num,
         })
     }
 
-    fn switch_page_events(&self) -> Stream<NotebookPage> {
+    fn switch_page_events(&self) -> EventStream<NotebookPage> {
         connect_stream!(self.connect_switch_page, |_, w, page_num| NotebookPage {
             child: w.clone(),
             page_num,
@@ -304,85 +654,218 @@ where
 
 /// Extension trait for `gtk::OverlayExt`.
 pub trait FrpOverlayExt {
-    fn stream_add_overlay<W: IsA<Widget> + 'static>(&self, stream: &Stream<W>);
+    fn stream_add_overlay<W: IsA<Widget> + 'static>(
+        &self,
+        stream: impl IntoBinding<W>,
+    ) -> Subscription;
 }
 
 impl<T> FrpOverlayExt for T
 where
     T: OverlayExt + ObjectExt + 'static,
 {
-    fn stream_add_overlay<W: IsA<Widget> + 'static>(&self, stream: &Stream<W>) {
+    fn stream_add_overlay<W: IsA<Widget> + 'static>(
+        &self,
+        stream: impl IntoBinding<W>,
+    ) -> Subscription {
         gtk_observe!(stream, |widget| self.add_overlay(widget.as_ref()))
     }
 }
 
 /// Extension trait for `gtk::RangeExt`.
 pub trait FrpRangeExt {
-    fn change_value_events(&self, inhibit: bool) -> Stream<RangeValue>;
+    fn stream_value(&self, stream: impl IntoBinding<f64>) -> Subscription;
+    /// Sets the limits and step of the adjustment. The value is clamped to the new limits.
+    fn stream_bounds(&self, stream: impl IntoBinding<ValueBounds>) -> Subscription;
+    fn change_value_events(&self, inhibit: bool) -> EventStream<RangeValue>;
+    /// Like `change_value_events`, but the propagation of each event is decided by `inhibit`.
+    fn change_value_events_with<F>(&self, inhibit: F) -> EventStream<RangeValue>
+    where
+        F: Fn(&RangeValue) -> bool + 'static;
+    /// Returns a Stream with the value every time it changes, including changes made by the
+    /// program.
+    fn value_changed_events(&self) -> EventStream<f64>;
 }
 
 impl<T> FrpRangeExt for T
 where
-    T: RangeExt,
+    T: IsA<gtk::Range>,
 {
-    fn change_value_events(&self, inhibit: bool) -> Stream<RangeValue> {
-        connect_stream!(self.connect_change_value, |_, scroll, value| RangeValue{ scroll, value }; Inhibit(inhibit))
+    fn stream_value(&self, stream: impl IntoBinding<f64>) -> Subscription {
+        gtk_observe!(stream, |v| self.set_value(*v))
+    }
+
+    fn stream_bounds(&self, stream: impl IntoBinding<ValueBounds>) -> Subscription {
+        bind_object(self, stream, |range, bounds| {
+            set_bounds(&range.adjustment(), bounds)
+        })
+    }
+
+    fn change_value_events(&self, inhibit: bool) -> EventStream<RangeValue> {
+        self.change_value_events_with(move |_| inhibit)
+    }
+
+    fn change_value_events_with<F>(&self, inhibit: F) -> EventStream<RangeValue>
+    where
+        F: Fn(&RangeValue) -> bool + 'static,
+    {
+        connect_stream!(self.connect_change_value, |_, scroll, value| RangeValue{ scroll, value } => inhibit)
+    }
+
+    fn value_changed_events(&self) -> EventStream<f64> {
+        connect_stream!(self.connect_value_changed, |range| range.value())
+    }
+}
+
+/// Extension trait for `gtk::SpinButton`.
+pub trait FrpSpinButtonExt {
+    fn stream_value(&self, stream: impl IntoBinding<f64>) -> Subscription;
+    /// Sets the limits and step of the adjustment. The value is clamped to the new limits.
+    fn stream_bounds(&self, stream: impl IntoBinding<ValueBounds>) -> Subscription;
+    /// Returns a Stream with the value every time it changes.
+    fn value_changed_events(&self) -> EventStream<f64>;
+}
+
+impl<T> FrpSpinButtonExt for T
+where
+    T: IsA<gtk::SpinButton>,
+{
+    fn stream_value(&self, stream: impl IntoBinding<f64>) -> Subscription {
+        gtk_observe!(stream, |v| self.set_value(*v))
+    }
+
+    fn stream_bounds(&self, stream: impl IntoBinding<ValueBounds>) -> Subscription {
+        bind_object(self, stream, |spin, bounds| {
+            set_bounds(&spin.adjustment(), bounds)
+        })
+    }
+
+    fn value_changed_events(&self) -> EventStream<f64> {
+        connect_stream!(self.connect_value_changed, |spin| spin.value())
     }
 }
 
 /// Extension trait for `gtk::SpinnerExt`.
 pub trait FrpSpinnerExt {
-    fn stream_active(&self, stream: &Stream<bool>);
+    fn stream_active(&self, stream: impl IntoBinding<bool>) -> Subscription;
 }
 
 impl<T> FrpSpinnerExt for T
 where
     T: SpinnerExt + ObjectExt + 'static,
 {
-    fn stream_active(&self, stream: &Stream<bool>) {
+    fn stream_active(&self, stream: impl IntoBinding<bool>) -> Subscription {
         gtk_observe!(stream, |active| self.set_active(*active))
     }
 }
 
+/// Extension trait for `gtk::Switch`.
+pub trait FrpSwitchExt {
+    fn stream_active(&self, stream: impl IntoBinding<bool>) -> Subscription;
+    /// Returns a Stream with the new state every time the switch is turned on or off.
+    fn state_set_events(&self) -> EventStream<bool>;
+    /// Same as `state_set_events`, but asks `allow` first if the state can be changed.
+    ///
+    /// When `allow` returns `false` the switch goes back to it's previous state, and the change
+    /// is not sent to the Stream.
+    fn state_set_events_with<F>(&self, allow: F) -> EventStream<bool>
+    where
+        F: Fn(bool) -> bool + 'static;
+}
+
+impl<T> FrpSwitchExt for T
+where
+    T: IsA<gtk::Switch>,
+{
+    fn stream_active(&self, stream: impl IntoBinding<bool>) -> Subscription {
+        gtk_observe!(stream, |b| self.set_active(*b))
+    }
+
+    fn state_set_events(&self) -> EventStream<bool> {
+        connect_stream!(self.connect_state_set, |_, state| state; Inhibit(false))
+    }
+
+    fn state_set_events_with<F>(&self, allow: F) -> EventStream<bool>
+    where
+        F: Fn(bool) -> bool + 'static,
+    {
+        let sink = Sink::new();
+        let stream = sink.stream();
+        let id = self.connect_state_set(move |switch, state| {
+            if switch.state() == state {
+                // reverting a vetoed change, or nothing changed
+                return Inhibit(false);
+            }
+            if allow(state) {
+                sink.send(state);
+                Inhibit(false)
+            } else {
+                // the active property already changed, so put it back later
+                let weak = switch.downgrade();
+                glib::idle_add_local_once(move || {
+                    if let Some(switch) = weak.upgrade() {
+                        switch.set_active(switch.state());
+                    }
+                });
+                Inhibit(true)
+            }
+        });
+        EventStream::new(stream, self, id)
+    }
+}
+
 /// Extension trait for `gtk::TextBufferExt`.
 pub trait FrpTextBufferExt {
-    fn stream_modified(&self, stream: &Stream<bool>);
-    fn stream_text(&self, stream: &Stream<String>);
-    fn changed_events(&self) -> Stream<()>;
-    fn modified_changed_events(&self) -> Stream<bool>;
+    fn stream_modified(&self, stream: impl IntoBinding<bool>) -> Subscription;
+    fn stream_text(&self, stream: impl IntoBinding<String>) -> Subscription;
+    fn changed_events(&self) -> EventStream<()>;
+    fn modified_changed_events(&self) -> EventStream<bool>;
 }
 
 impl<T> FrpTextBufferExt for T
 where
     T: TextBufferExt + ObjectExt + 'static,
 {
-    fn stream_modified(&self, stream: &Stream<bool>) {
+    fn stream_modified(&self, stream: impl IntoBinding<bool>) -> Subscription {
         gtk_observe!(stream, |b| self.set_modified(*b))
     }
 
-    fn stream_text(&self, stream: &Stream<String>) {
+    fn stream_text(&self, stream: impl IntoBinding<String>) -> Subscription {
         gtk_observe!(stream, |s| self.set_text(&s))
     }
 
-    fn changed_events(&self) -> Stream<()> {
+    fn changed_events(&self) -> EventStream<()> {
         connect_stream!(self.connect_changed)
     }
 
-    fn modified_changed_events(&self) -> Stream<bool> {
+    fn modified_changed_events(&self) -> EventStream<bool> {
         connect_stream!(self.connect_modified_changed, |this| this.is_modified())
     }
 }
 
 /// Extension trait for `gtk::ToggleButtonExt`.
-trait FrpToggleButtonExt {
-    fn toggled_events(&self) -> Stream<ToggleState>;
+pub trait FrpToggleButtonExt {
+    fn stream_active(&self, stream: impl IntoBinding<bool>) -> Subscription;
+    fn stream_toggle_state(&self, stream: impl IntoBinding<ToggleState>) -> Subscription;
+    fn toggled_events(&self) -> EventStream<ToggleState>;
 }
 
 impl<T> FrpToggleButtonExt for T
 where
-    T: ToggleButtonExt,
+    T: IsA<gtk::ToggleButton>,
 {
-    fn toggled_events(&self) -> Stream<ToggleState> {
+    fn stream_active(&self, stream: impl IntoBinding<bool>) -> Subscription {
+        gtk_observe!(stream, |b| self.set_active(*b))
+    }
+
+    fn stream_toggle_state(&self, stream: impl IntoBinding<ToggleState>) -> Subscription {
+        bind_object(self, stream, |button, state| {
+            button.set_inconsistent(state.inconsistent);
+            button.set_active(state.active);
+        })
+    }
+
+    fn toggled_events(&self) -> EventStream<ToggleState> {
         connect_stream!(self.connect_toggled, |this| ToggleState {
             active: this.is_active(),
             inconsistent: this.is_inconsistent(),
@@ -393,68 +876,506 @@ where
 /// Extension trait for `gtk::ButtonExt`.
 pub trait FrpToolButtonExt {
     /// Returns a `clicked` event Stream.
-    fn clicked_events(&self) -> Stream<()>;
+    fn clicked_events(&self) -> EventStream<()>;
 }
 
 impl<T> FrpToolButtonExt for T
 where
-    T: ToolButtonExt,
+    T: ToolButtonExt + ObjectExt,
 {
-    fn clicked_events(&self) -> Stream<()> {
+    fn clicked_events(&self) -> EventStream<()> {
         connect_stream!(self.connect_clicked)
     }
 }
 
+/// Extension trait for `gtk::TreeSelection`.
+pub trait FrpTreeSelectionExt {
+    /// Returns a Stream with the paths of the selected rows every time the selection changes.
+    fn changed_events(&self) -> EventStream<Vec<gtk::TreePath>>;
+    /// Returns a Stream with the values of a model column for the selected rows every time the
+    /// selection changes.
+    ///
+    /// Values that can't be converted into `V` are skipped.
+    fn selected_values_events<V>(&self, column: i32) -> EventStream<Vec<V>>
+    where
+        V: for<'b> FromValue<'b> + 'static;
+    /// Returns a Signal with the paths of the selected rows.
+    fn selection_signal(&self) -> Signal<Vec<gtk::TreePath>>;
+}
+
+impl<T> FrpTreeSelectionExt for T
+where
+    T: IsA<gtk::TreeSelection>,
+{
+    fn changed_events(&self) -> EventStream<Vec<gtk::TreePath>> {
+        connect_stream!(self.connect_changed, |sel| sel.selected_rows().0)
+    }
+
+    fn selected_values_events<V>(&self, column: i32) -> EventStream<Vec<V>>
+    where
+        V: for<'b> FromValue<'b> + 'static,
+    {
+        connect_stream!(self.connect_changed, |sel| {
+            let (paths, model) = sel.selected_rows();
+            paths
+                .iter()
+                .filter_map(|path| model.iter(path))
+                .filter_map(|iter| model.value(&iter, column).get().ok())
+                .collect::<Vec<V>>()
+        })
+    }
+
+    fn selection_signal(&self) -> Signal<Vec<gtk::TreePath>> {
+        cached_signal(
+            self,
+            |sel, dirty| sel.connect_changed(move |_| dirty()),
+            |sel| sel.selected_rows().0,
+        )
+    }
+}
+
+/// Extension trait for `gtk::TreeView`.
+pub trait FrpTreeViewExt {
+    /// Returns a Stream with the activated rows.
+    fn row_activated_events(&self) -> EventStream<TreeRowActivated>;
+}
+
+impl<T> FrpTreeViewExt for T
+where
+    T: IsA<gtk::TreeView>,
+{
+    fn row_activated_events(&self) -> EventStream<TreeRowActivated> {
+        connect_stream!(self.connect_row_activated, |_, path, column| {
+            TreeRowActivated {
+                path: path.clone(),
+                column: column.clone(),
+            }
+        })
+    }
+}
+
 /// Extension trait for `gtk::WidgetExt`.
 pub trait FrpWidgetExt {
-    fn stream_sensitive(&self, stream: &Stream<bool>);
-    fn stream_visible(&self, stream: &Stream<bool>);
-    fn delete_events(&self, inhibit: bool) -> Stream<()>;
-    fn enter_notify_events(&self, inhibit: bool) -> Stream<gdk::EventCrossing>;
-    fn leave_notify_events(&self, inhibit: bool) -> Stream<gdk::EventCrossing>;
-    fn motion_notify_events(&self, inhibit: bool) -> Stream<gdk::EventMotion>;
-    fn show_events(&self) -> Stream<()>;
-    fn hide_events(&self) -> Stream<()>;
+    fn stream_sensitive(&self, stream: impl IntoBinding<bool>) -> Subscription;
+    fn stream_visible(&self, stream: impl IntoBinding<bool>) -> Subscription;
+    fn delete_events(&self, inhibit: bool) -> EventStream<()>;
+    /// Like `delete_events`, but the propagation of each event is decided by `inhibit`.
+    ///
+    /// Returning `true` keeps the window open, so a `Signal<bool>` like `modified` can block
+    /// closing with `move || modified.sample()`.
+    fn delete_events_with<F>(&self, inhibit: F) -> EventStream<()>
+    where
+        F: Fn() -> bool + 'static;
+    fn enter_notify_events(&self, inhibit: bool) -> EventStream<gdk::EventCrossing>;
+    /// Like `enter_notify_events`, but the propagation of each event is decided by `inhibit`.
+    fn enter_notify_events_with<F>(&self, inhibit: F) -> EventStream<gdk::EventCrossing>
+    where
+        F: Fn(&gdk::EventCrossing) -> bool + 'static;
+    /// Like `enter_notify_events`, but emits the event data as a `CrossingEvent` that can be
+    /// sent to other threads.
+    fn enter_notify_events_data(&self, inhibit: bool) -> EventStream<CrossingEvent>;
+    /// Like `enter_notify_events_data`, but the propagation of each event is decided by `inhibit`.
+    fn enter_notify_events_data_with<F>(&self, inhibit: F) -> EventStream<CrossingEvent>
+    where
+        F: Fn(&CrossingEvent) -> bool + 'static;
+    fn leave_notify_events(&self, inhibit: bool) -> EventStream<gdk::EventCrossing>;
+    /// Like `leave_notify_events`, but the propagation of each event is decided by `inhibit`.
+    fn leave_notify_events_with<F>(&self, inhibit: F) -> EventStream<gdk::EventCrossing>
+    where
+        F: Fn(&gdk::EventCrossing) -> bool + 'static;
+    /// Like `leave_notify_events`, but emits the event data as a `CrossingEvent` that can be
+    /// sent to other threads.
+    fn leave_notify_events_data(&self, inhibit: bool) -> EventStream<CrossingEvent>;
+    /// Like `leave_notify_events_data`, but the propagation of each event is decided by `inhibit`.
+    fn leave_notify_events_data_with<F>(&self, inhibit: F) -> EventStream<CrossingEvent>
+    where
+        F: Fn(&CrossingEvent) -> bool + 'static;
+    fn motion_notify_events(&self, inhibit: bool) -> EventStream<gdk::EventMotion>;
+    /// Like `motion_notify_events`, but the propagation of each event is decided by `inhibit`.
+    fn motion_notify_events_with<F>(&self, inhibit: F) -> EventStream<gdk::EventMotion>
+    where
+        F: Fn(&gdk::EventMotion) -> bool + 'static;
+    /// Like `motion_notify_events`, but emits the event data as a `PointerEvent` that can be
+    /// sent to other threads.
+    fn motion_notify_events_data(&self, inhibit: bool) -> EventStream<PointerEvent>;
+    /// Like `motion_notify_events_data`, but the propagation of each event is decided by `inhibit`.
+    fn motion_notify_events_data_with<F>(&self, inhibit: F) -> EventStream<PointerEvent>
+    where
+        F: Fn(&PointerEvent) -> bool + 'static;
+    /// Returns a Stream with the mouse button presses. This enables button events on the widget.
+    fn button_press_events(&self, inhibit: bool) -> EventStream<ButtonEvent>;
+    /// Like `button_press_events`, but the propagation of each event is decided by `inhibit`.
+    fn button_press_events_with<F>(&self, inhibit: F) -> EventStream<ButtonEvent>
+    where
+        F: Fn(&ButtonEvent) -> bool + 'static;
+    /// Returns a Stream with the mouse button releases. This enables button events on the widget.
+    fn button_release_events(&self, inhibit: bool) -> EventStream<ButtonEvent>;
+    /// Like `button_release_events`, but the propagation of each event is decided by `inhibit`.
+    fn button_release_events_with<F>(&self, inhibit: F) -> EventStream<ButtonEvent>
+    where
+        F: Fn(&ButtonEvent) -> bool + 'static;
+    /// Returns a Stream with the scroll events. This enables smooth scrolling on the widget, so
+    /// most devices will report their deltas with the `Smooth` direction.
+    fn scroll_events(&self, inhibit: bool) -> EventStream<ScrollEvent>;
+    /// Like `scroll_events`, but the propagation of each event is decided by `inhibit`.
+    fn scroll_events_with<F>(&self, inhibit: F) -> EventStream<ScrollEvent>
+    where
+        F: Fn(&ScrollEvent) -> bool + 'static;
+    /// Returns a Stream with the single and multiple clicks of a mouse button.
+    ///
+    /// Use button `0` to listen to all buttons.
+    fn multi_press_events(&self, button: u32) -> EventStream<MultiPressEvent>;
+    /// Returns a Stream with the begin, updates and end of drag gestures.
+    fn drag_events(&self) -> EventStream<DragEvent>;
+    /// Returns a Stream with the position of long press gestures.
+    fn long_press_events(&self) -> EventStream<LongPressEvent>;
+    /// Returns a Stream with the scale of pinch-zoom gestures, relative to the start of the
+    /// gesture.
+    fn zoom_events(&self) -> EventStream<f64>;
+    fn key_press_events(&self, inhibit: bool) -> EventStream<KeyEvent>;
+    /// Like `key_press_events`, but the propagation of each event is decided by `inhibit`.
+    fn key_press_events_with<F>(&self, inhibit: F) -> EventStream<KeyEvent>
+    where
+        F: Fn(&KeyEvent) -> bool + 'static;
+    fn key_release_events(&self, inhibit: bool) -> EventStream<KeyEvent>;
+    /// Like `key_release_events`, but the propagation of each event is decided by `inhibit`.
+    fn key_release_events_with<F>(&self, inhibit: F) -> EventStream<KeyEvent>
+    where
+        F: Fn(&KeyEvent) -> bool + 'static;
+    /// Returns a Stream that fires when the specified key combination is pressed.
+    ///
+    /// The accelerator uses the `gtk::accelerator_parse` format, like `"<Control>s"`. Matching
+    /// key presses are not propagated further. Fails if the accelerator can't be parsed.
+    fn shortcut_events(&self, accelerator: &str) -> Result<EventStream<()>, AcceleratorError>;
+    fn show_events(&self) -> EventStream<()>;
+    fn hide_events(&self) -> EventStream<()>;
+    /// Returns a Stream with the frame time (in microseconds) of every frame.
+    ///
+    /// This keeps the frame clock running while the widget is mapped, until the Stream is dropped.
+    fn tick_events(&self) -> EventStream<i64>;
+    /// Binds a property to a model in both directions.
+    ///
+    /// Values from the model Signal are written to the property, and changes made to the property
+    /// (usually from user input) are sent to the model Sink. Values that are already in sync aren't
+    /// sent back, so the model and the widget don't feed each other in a loop. If the widget
+    /// adjusts a written value (like a SpinButton clamping it), the adjusted value is sent back.
+    ///
+    /// Fails if the property doesn't exist, isn't both readable and writable, or has a type
+    /// incompatible with `V`. The Signal is checked for changes as described in
+    /// `SignalExt::changes`.
+    /// `gtk::Range` widgets (like `gtk::Scale`) keep their value on a `gtk::Adjustment`, so for them
+    /// the `value` property is bound on the adjustment.
+    fn bind_bidirectional<V>(
+        &self,
+        name: &str,
+        sink: &Sink<V>,
+        signal: &Signal<V>,
+    ) -> Result<Subscription, PropertyError>
+    where
+        V: ToValue + for<'b> FromValue<'b> + StaticType + PartialEq + Clone + 'static;
     fn show_(self) -> Self;
     fn show_all_(self) -> Self;
 }
 
 impl<T> FrpWidgetExt for T
 where
-    T: WidgetExt + ObjectExt + 'static,
+    T: WidgetExt + ObjectExt + IsA<Widget> + 'static,
 {
-    fn stream_sensitive(&self, stream: &Stream<bool>) {
+    fn stream_sensitive(&self, stream: impl IntoBinding<bool>) -> Subscription {
         gtk_observe!(stream, |b| self.set_sensitive(*b))
     }
 
-    fn stream_visible(&self, stream: &Stream<bool>) {
+    fn stream_visible(&self, stream: impl IntoBinding<bool>) -> Subscription {
         gtk_observe!(stream, |b| self.set_visible(*b))
     }
 
-    fn delete_events(&self, inhibit: bool) -> Stream<()> {
-        connect_stream!(self.connect_delete_event, |_, _| (); Inhibit(inhibit))
+    fn delete_events(&self, inhibit: bool) -> EventStream<()> {
+        self.delete_events_with(move || inhibit)
     }
 
-    fn enter_notify_events(&self, inhibit: bool) -> Stream<gdk::EventCrossing> {
-        connect_stream!(self.connect_enter_notify_event, |_, ev| ev; Inhibit(inhibit))
+    fn delete_events_with<F>(&self, inhibit: F) -> EventStream<()>
+    where
+        F: Fn() -> bool + 'static,
+    {
+        connect_stream!(self.connect_delete_event, |_, _| () => move |_: &()| inhibit())
+    }
+
+    fn enter_notify_events(&self, inhibit: bool) -> EventStream<gdk::EventCrossing> {
+        self.enter_notify_events_with(move |_| inhibit)
+    }
+
+    fn enter_notify_events_with<F>(&self, inhibit: F) -> EventStream<gdk::EventCrossing>
+    where
+        F: Fn(&gdk::EventCrossing) -> bool + 'static,
+    {
+        connect_stream!(self.connect_enter_notify_event, |_, ev| ev => inhibit)
+    }
+
+    fn enter_notify_events_data(&self, inhibit: bool) -> EventStream<CrossingEvent> {
+        self.enter_notify_events_data_with(move |_| inhibit)
+    }
+
+    fn enter_notify_events_data_with<F>(&self, inhibit: F) -> EventStream<CrossingEvent>
+    where
+        F: Fn(&CrossingEvent) -> bool + 'static,
+    {
+        connect_stream!(self.connect_enter_notify_event, |_, ev| CrossingEvent::from(ev) => inhibit)
+    }
+
+    fn leave_notify_events(&self, inhibit: bool) -> EventStream<gdk::EventCrossing> {
+        self.leave_notify_events_with(move |_| inhibit)
+    }
+
+    fn leave_notify_events_with<F>(&self, inhibit: F) -> EventStream<gdk::EventCrossing>
+    where
+        F: Fn(&gdk::EventCrossing) -> bool + 'static,
+    {
+        connect_stream!(self.connect_leave_notify_event, |_, ev| ev => inhibit)
+    }
+
+    fn leave_notify_events_data(&self, inhibit: bool) -> EventStream<CrossingEvent> {
+        self.leave_notify_events_data_with(move |_| inhibit)
+    }
+
+    fn leave_notify_events_data_with<F>(&self, inhibit: F) -> EventStream<CrossingEvent>
+    where
+        F: Fn(&CrossingEvent) -> bool + 'static,
+    {
+        connect_stream!(self.connect_leave_notify_event, |_, ev| CrossingEvent::from(ev) => inhibit)
+    }
+
+    fn motion_notify_events(&self, inhibit: bool) -> EventStream<gdk::EventMotion> {
+        self.motion_notify_events_with(move |_| inhibit)
+    }
+
+    fn motion_notify_events_with<F>(&self, inhibit: F) -> EventStream<gdk::EventMotion>
+    where
+        F: Fn(&gdk::EventMotion) -> bool + 'static,
+    {
+        connect_stream!(self.connect_motion_notify_event, |_, ev| ev => inhibit)
+    }
+
+    fn motion_notify_events_data(&self, inhibit: bool) -> EventStream<PointerEvent> {
+        self.motion_notify_events_data_with(move |_| inhibit)
+    }
+
+    fn motion_notify_events_data_with<F>(&self, inhibit: F) -> EventStream<PointerEvent>
+    where
+        F: Fn(&PointerEvent) -> bool + 'static,
+    {
+        connect_stream!(self.connect_motion_notify_event, |_, ev| PointerEvent::from(ev) => inhibit)
+    }
+
+    fn button_press_events(&self, inhibit: bool) -> EventStream<ButtonEvent> {
+        self.button_press_events_with(move |_| inhibit)
+    }
+
+    fn button_press_events_with<F>(&self, inhibit: F) -> EventStream<ButtonEvent>
+    where
+        F: Fn(&ButtonEvent) -> bool + 'static,
+    {
+        self.add_events(gdk::EventMask::BUTTON_PRESS_MASK);
+        connect_stream!(self.connect_button_press_event, |_, ev| ButtonEvent::from(ev) => inhibit)
+    }
+
+    fn button_release_events(&self, inhibit: bool) -> EventStream<ButtonEvent> {
+        self.button_release_events_with(move |_| inhibit)
+    }
+
+    fn button_release_events_with<F>(&self, inhibit: F) -> EventStream<ButtonEvent>
+    where
+        F: Fn(&ButtonEvent) -> bool + 'static,
+    {
+        self.add_events(gdk::EventMask::BUTTON_RELEASE_MASK);
+        connect_stream!(self.connect_button_release_event, |_, ev| ButtonEvent::from(ev) => inhibit)
+    }
+
+    fn scroll_events(&self, inhibit: bool) -> EventStream<ScrollEvent> {
+        self.scroll_events_with(move |_| inhibit)
+    }
+
+    fn scroll_events_with<F>(&self, inhibit: F) -> EventStream<ScrollEvent>
+    where
+        F: Fn(&ScrollEvent) -> bool + 'static,
+    {
+        self.add_events(gdk::EventMask::SCROLL_MASK | gdk::EventMask::SMOOTH_SCROLL_MASK);
+        connect_stream!(self.connect_scroll_event, |_, ev| ScrollEvent::from(ev) => inhibit)
+    }
+
+    fn multi_press_events(&self, button: u32) -> EventStream<MultiPressEvent> {
+        let gesture = gtk::GestureMultiPress::new(self);
+        gesture.set_button(button);
+        connect_gesture(gesture, |gesture, sink| {
+            gesture.connect_pressed(move |_, n_press, x, y| {
+                sink.send(MultiPressEvent { n_press, x, y })
+            });
+        })
     }
 
-    fn leave_notify_events(&self, inhibit: bool) -> Stream<gdk::EventCrossing> {
-        connect_stream!(self.connect_leave_notify_event, |_, ev| ev; Inhibit(inhibit))
+    fn drag_events(&self) -> EventStream<DragEvent> {
+        let gesture = gtk::GestureDrag::new(self);
+        connect_gesture(gesture, |gesture, sink| {
+            let drag_event = |gesture: &gtk::GestureDrag, phase, offset_x, offset_y| {
+                let (start_x, start_y) = gesture.start_point().unwrap_or_default();
+                DragEvent {
+                    phase,
+                    start_x,
+                    start_y,
+                    offset_x,
+                    offset_y,
+                }
+            };
+            let sink_ = sink.clone();
+            gesture.connect_drag_begin(move |g, _, _| {
+                sink_.send(drag_event(g, DragPhase::Begin, 0.0, 0.0))
+            });
+            let sink_ = sink.clone();
+            gesture.connect_drag_update(move |g, x, y| {
+                sink_.send(drag_event(g, DragPhase::Update, x, y))
+            });
+            gesture.connect_drag_end(move |g, x, y| sink.send(drag_event(g, DragPhase::End, x, y)));
+        })
+    }
+
+    fn long_press_events(&self) -> EventStream<LongPressEvent> {
+        let gesture = gtk::GestureLongPress::new(self);
+        connect_gesture(gesture, |gesture, sink| {
+            gesture.connect_pressed(move |_, x, y| sink.send(LongPressEvent { x, y }));
+        })
+    }
+
+    fn zoom_events(&self) -> EventStream<f64> {
+        let gesture = gtk::GestureZoom::new(self);
+        connect_gesture(gesture, |gesture, sink| {
+            gesture.connect_scale_changed(move |_, scale| sink.send(scale));
+        })
     }
 
-    fn motion_notify_events(&self, inhibit: bool) -> Stream<gdk::EventMotion> {
-        connect_stream!(self.connect_motion_notify_event, |_, ev| ev; Inhibit(inhibit))
+    fn key_press_events(&self, inhibit: bool) -> EventStream<KeyEvent> {
+        self.key_press_events_with(move |_| inhibit)
+    }
+
+    fn key_press_events_with<F>(&self, inhibit: F) -> EventStream<KeyEvent>
+    where
+        F: Fn(&KeyEvent) -> bool + 'static,
+    {
+        connect_stream!(self.connect_key_press_event, |_, ev| KeyEvent::from(ev) => inhibit)
     }
 
-    fn show_events(&self) -> Stream<()> {
+    fn key_release_events(&self, inhibit: bool) -> EventStream<KeyEvent> {
+        self.key_release_events_with(move |_| inhibit)
+    }
+
+    fn key_release_events_with<F>(&self, inhibit: F) -> EventStream<KeyEvent>
+    where
+        F: Fn(&KeyEvent) -> bool + 'static,
+    {
+        connect_stream!(self.connect_key_release_event, |_, ev| KeyEvent::from(ev) => inhibit)
+    }
+
+    fn shortcut_events(&self, accelerator: &str) -> Result<EventStream<()>, AcceleratorError> {
+        let (key, mods) = gtk::accelerator_parse(accelerator);
+        if key == 0 {
+            return Err(AcceleratorError(accelerator.to_owned()));
+        }
+        let key = gdk::keys::Key::from(key);
+        let sink = Sink::new();
+        let stream = sink.stream();
+        let id = self.connect_key_press_event(move |_, ev| {
+            let state = ev.state() & gtk::accelerator_get_default_mod_mask();
+            if ev.keyval().to_lower() == key && state == mods {
+                sink.send(());
+                Inhibit(true)
+            } else {
+                Inhibit(false)
+            }
+        });
+        Ok(EventStream::new(stream, self, id))
+    }
+
+    fn show_events(&self) -> EventStream<()> {
         connect_stream!(self.connect_show)
     }
 
-    fn hide_events(&self) -> Stream<()> {
+    fn hide_events(&self) -> EventStream<()> {
         connect_stream!(self.connect_hide)
     }
 
+    fn tick_events(&self) -> EventStream<i64> {
+        let sink = Sink::new();
+        let stream = sink.stream();
+        let id = self.add_tick_callback(move |_, clock| {
+            sink.send(clock.frame_time());
+            Continue(true)
+        });
+        EventStream::from_disconnector(stream, Disconnector::new(move || id.remove()))
+    }
+
+    fn bind_bidirectional<V>(
+        &self,
+        name: &str,
+        sink: &Sink<V>,
+        signal: &Signal<V>,
+    ) -> Result<Subscription, PropertyError>
+    where
+        V: ToValue + for<'b> FromValue<'b> + StaticType + PartialEq + Clone + 'static,
+    {
+        let widget: &Widget = self.as_ref();
+        let target: glib::Object = match widget.dynamic_cast_ref::<gtk::Range>() {
+            Some(range) if widget.find_property(name).is_none() => range.adjustment().upcast(),
+            _ => widget.clone().upcast(),
+        };
+        check_readable_property(&target, name, V::static_type())?;
+        check_writable_property(&target, name, V::static_type())?;
+        let state = Rc::new(BindingState {
+            updating: Cell::new(false),
+            last: RefCell::new(signal.sample()),
+        });
+        if let Some(val) = state.write(&target, name) {
+            sink.send(val);
+        }
+
+        let prop = name.to_owned();
+        let state_ = state.clone();
+        let sink_ = sink.clone();
+        let notify_id = target.connect_notify_local(Some(name), move |obj, _| {
+            if state_.updating.get() {
+                return;
+            }
+            if let Ok(val) = obj.try_property::<V>(&prop) {
+                if *state_.last.borrow() != val {
+                    state_.last.replace(val.clone());
+                    sink_.send(val);
+                }
+            }
+        });
+
+        let changes = Sink::new();
+        let model = Fragile::new((state, target.downgrade(), name.to_owned(), sink.clone()));
+        changes.stream().observe(move |val| {
+            let (state, weak, prop, sink) = model.get();
+            if *state.last.borrow() != *val {
+                state.last.replace(val.into_owned());
+                if let Some(adjusted) = weak.upgrade().and_then(|obj| state.write(&obj, prop)) {
+                    sink.send(adjusted);
+                }
+            }
+        });
+        let poller = Arc::new(poll_signal(signal, changes));
+        let poller_ = Arc::downgrade(&poller);
+        let destroy_id = widget.connect_destroy(move |_| {
+            if let Some(poller) = poller_.upgrade() {
+                poller.disconnect()
+            }
+        });
+
+        let mut sub = Subscription::new();
+        sub.add_handler(Disconnector::signal(&target, notify_id));
+        sub.add_handler(Disconnector::signal(widget, destroy_id));
+        sub.add_handler(Disconnector::new(move || poller.disconnect()));
+        Ok(sub)
+    }
+
     fn show_(self) -> Self {
         self.show();
         self
@@ -471,6 +1392,30 @@ pub trait FrpObjectExt {
     fn wrap_fragile(&self) -> Fragile<Self>
     where
         Self: Sized;
+    /// Returns a Stream with the new value of a property every time it changes.
+    ///
+    /// Fails if the property doesn't exist, isn't readable or has a type incompatible with `V`.
+    fn property_stream<V>(&self, name: &str) -> Result<EventStream<V>, PropertyError>
+    where
+        V: for<'b> FromValue<'b> + StaticType + 'static;
+    /// Returns a Signal with the value of a property.
+    ///
+    /// The value is cached and only read again after the property changes. Fails in the same
+    /// cases as `property_stream`, or if the property has no value and `V` isn't an `Option`.
+    /// If a later read fails, the Signal keeps the last value that was read.
+    fn property_signal<V>(&self, name: &str) -> Result<Signal<V>, PropertyError>
+    where
+        V: for<'b> FromValue<'b> + StaticType + Clone + 'static;
+    /// Sets a property with the values from a Stream or Signal.
+    ///
+    /// Fails if the property doesn't exist, isn't writable or has a type incompatible with `V`.
+    fn stream_property<V>(
+        &self,
+        name: &str,
+        stream: impl IntoBinding<V>,
+    ) -> Result<Subscription, PropertyError>
+    where
+        V: ToValue + StaticType + 'static;
 }
 
 impl<T> FrpObjectExt for T
@@ -480,4 +1425,535 @@ where
     fn wrap_fragile(&self) -> Fragile<Self> {
         Fragile::new(self.clone())
     }
+
+    fn property_stream<V>(&self, name: &str) -> Result<EventStream<V>, PropertyError>
+    where
+        V: for<'b> FromValue<'b> + StaticType + 'static,
+    {
+        check_readable_property(self, name, V::static_type())?;
+        let prop = name.to_owned();
+        let sink = Sink::new();
+        let stream = sink.stream();
+        let id = self.connect_notify_local(Some(name), move |this, _| {
+            if let Ok(val) = this.try_property::<V>(&prop) {
+                sink.send(val)
+            }
+        });
+        Ok(EventStream::new(stream, self, id))
+    }
+
+    fn property_signal<V>(&self, name: &str) -> Result<Signal<V>, PropertyError>
+    where
+        V: for<'b> FromValue<'b> + StaticType + Clone + 'static,
+    {
+        check_readable_property(self, name, V::static_type())?;
+        // the type was checked already, so this only fails on a NULL value
+        let last = self
+            .try_property::<V>(name)
+            .map_err(|_| PropertyError::Unset {
+                type_name: self.type_().to_string(),
+                property: name.to_owned(),
+            })?;
+        let last = RefCell::new(last);
+        let prop = name.to_owned();
+        Ok(cached_signal(
+            self,
+            |obj, dirty| obj.connect_notify_local(Some(name), move |_, _| dirty()),
+            move |obj| {
+                if let Ok(val) = obj.try_property(&prop) {
+                    *last.borrow_mut() = val;
+                }
+                last.borrow().clone()
+            },
+        ))
+    }
+
+    fn stream_property<V>(
+        &self,
+        name: &str,
+        stream: impl IntoBinding<V>,
+    ) -> Result<Subscription, PropertyError>
+    where
+        V: ToValue + StaticType + 'static,
+    {
+        check_writable_property(self, name, V::static_type())?;
+        let prop = name.to_owned();
+        Ok(bind_object(self, stream, move |obj, val| {
+            // the type was checked already, but the value can still be out of range
+            if let Err(e) = obj.try_set_property(&prop, val) {
+                glib::g_warning!("frappe-gtk", "{}", e);
+            }
+        }))
+    }
+}
+
+/// Shared state of a two-way property binding.
+struct BindingState<V> {
+    /// Set while we're writing the property, so we can ignore our own changes.
+    updating: Cell<bool>,
+    /// Last value that was known to be the same on both sides.
+    last: RefCell<V>,
+}
+
+impl<V> BindingState<V>
+where
+    V: ToValue + for<'b> FromValue<'b> + StaticType + PartialEq + Clone + 'static,
+{
+    /// Writes the last model value into the property.
+    ///
+    /// Returns the value that the object ended up with if it adjusted the written one (like a
+    /// SpinButton clamping it), so it can be sent back to the model.
+    fn write(&self, obj: &glib::Object, name: &str) -> Option<V> {
+        self.updating.set(true);
+        if let Err(e) = obj.try_set_property(name, &*self.last.borrow()) {
+            glib::g_warning!("frappe-gtk", "{}", e);
+        }
+        self.updating.set(false);
+        let val = obj.try_property::<V>(name).ok()?;
+        if *self.last.borrow() == val {
+            return None;
+        }
+        self.last.replace(val.clone());
+        Some(val)
+    }
+}
+
+/// Calls `f` on an object with the values from a Stream or Signal.
+///
+/// Same as `gtk_observe!`, but `f` receives the object.
+pub(crate) fn bind_object<O, V, F>(obj: &O, stream: impl IntoBinding<V>, f: F) -> Subscription
+where
+    O: ObjectType,
+    V: 'static,
+    F: Fn(&O, &V) + 'static,
+{
+    bind_weak(obj.downgrade(), stream, f)
+}
+
+/// Same as `bind_object`, but takes a weak reference to the object.
+///
+/// This is what `gtk_observe!` expands to, so it has to be public.
+#[doc(hidden)]
+pub fn bind_weak<O, V, F>(obj: WeakRef<O>, stream: impl IntoBinding<V>, f: F) -> Subscription
+where
+    O: ObjectType,
+    V: 'static,
+    F: Fn(&O, &V) + 'static,
+{
+    let mut sub = Subscription::new();
+    let target = match obj.upgrade() {
+        Some(target) => target,
+        None => return sub,
+    };
+    let mut binding = stream.into_binding();
+    // the observer lives in a stream owned by the binding, so removing the binding releases it
+    // and the stream chain right away, instead of on the next value
+    let stream = binding.stream().filter(|_| true);
+    let poller = binding.take_poller();
+    let teardown = Arc::new(Disconnector::new({
+        let stream = stream.clone();
+        move || {
+            drop(stream);
+            if let Some(poller) = poller {
+                poller.disconnect()
+            }
+        }
+    }));
+    // a detached binding is kept alive by it's own observer until the object is gone
+    let keepalive = teardown.clone();
+    let state = Fragile::new((obj, f));
+    stream.observe(move |val| {
+        let _keepalive = &keepalive;
+        let (weak, f) = state.get();
+        weak.upgrade().map(|obj| f(&obj, &val)).is_some()
+    });
+    if let Some(widget) = target.dynamic_cast_ref::<Widget>() {
+        let weak = Arc::downgrade(&teardown);
+        let id = widget.connect_destroy(move |_| {
+            if let Some(teardown) = weak.upgrade() {
+                teardown.disconnect()
+            }
+        });
+        sub.add_handler(Disconnector::signal(widget, id));
+    }
+    sub.add_handler(Disconnector::new(move || teardown.disconnect()));
+    binding.start();
+    sub
+}
+
+/// Sets the limits and step of an adjustment, clamping it's value.
+fn set_bounds(adj: &gtk::Adjustment, bounds: &ValueBounds) {
+    adj.configure(
+        adj.value(),
+        bounds.lower,
+        bounds.upper,
+        bounds.step,
+        adj.page_increment(),
+        adj.page_size(),
+    );
+}
+
+/// Changes the rows of a ComboBoxText to match a list of `(id, label)` pairs.
+fn update_combo_items(combo: &gtk::ComboBoxText, items: &[(String, String)]) {
+    let store = match combo
+        .model()
+        .and_then(|m| m.downcast::<gtk::ListStore>().ok())
+    {
+        Some(store) => store,
+        None => return,
+    };
+    let (id_col, text_col) = (combo.id_column(), combo.entry_text_column());
+    let id_of = |iter: &gtk::TreeIter| {
+        store
+            .value(iter, id_col)
+            .get::<Option<String>>()
+            .ok()
+            .flatten()
+    };
+    // removing the active row would unset it, so only the rows that are gone are removed
+    for n in (0..store.iter_n_children(None)).rev() {
+        let id = store.iter_nth_child(None, n).and_then(|iter| id_of(&iter));
+        if !items
+            .iter()
+            .any(|(item_id, _)| id.as_ref() == Some(item_id))
+        {
+            ComboBoxTextExt::remove(combo, n);
+        }
+    }
+    for (i, (id, label)) in items.iter().enumerate() {
+        let pos = i as i32;
+        let found = (pos..store.iter_n_children(None))
+            .filter_map(|n| Some((n, store.iter_nth_child(None, n)?)))
+            .find(|(_, iter)| id_of(iter).as_ref() == Some(id));
+        match found {
+            Some((n, iter)) => {
+                if n != pos {
+                    store.move_before(&iter, store.iter_nth_child(None, pos).as_ref());
+                }
+                if store.value(&iter, text_col).get::<String>().ok().as_ref() != Some(label) {
+                    store.set_value(&iter, text_col as u32, &label.to_value());
+                }
+            }
+            None => combo.insert(pos, Some(id), label),
+        }
+    }
+    // the rows left have ids that were repeated
+    while store.iter_n_children(None) > items.len() as i32 {
+        ComboBoxTextExt::remove(combo, items.len() as i32);
+    }
+}
+
+/// Sends the events of a gesture into an `EventStream`.
+///
+/// Gestures aren't owned by their widget, so the gesture is kept alive until the stream is dropped.
+fn connect_gesture<G, V, F>(gesture: G, connect: F) -> EventStream<V>
+where
+    G: IsA<gtk::EventController>,
+    V: 'static,
+    F: FnOnce(&G, Sink<V>),
+{
+    gesture.set_propagation_phase(gtk::PropagationPhase::Bubble);
+    let sink = Sink::new();
+    let stream = sink.stream();
+    connect(&gesture, sink);
+    EventStream::from_disconnector(stream, Disconnector::new(move || drop(gesture)))
+}
+
+/// Returns a Signal with a cached value that is read again after the object reports a change.
+///
+/// `connect` must connect a signal handler that calls the provided function on changes.
+fn cached_signal<O, V, C, R>(obj: &O, connect: C, read: R) -> Signal<V>
+where
+    O: ObjectType,
+    V: Clone + 'static,
+    C: FnOnce(&O, Box<dyn Fn()>) -> glib::SignalHandlerId,
+    R: Fn(&O) -> V + 'static,
+{
+    let value = RefCell::new(read(obj));
+    let dirty = Rc::new(Cell::new(false));
+    let dirty_ = dirty.clone();
+    let id = connect(obj, Box::new(move || dirty_.set(true)));
+    let handler = Disconnector::signal(obj, id);
+    let state = Fragile::new((obj.downgrade(), value, dirty, read));
+    Signal::from_fn(move || {
+        let _keepalive = &handler;
+        let (weak, value, dirty, read) = state.get();
+        if dirty.replace(false) {
+            if let Some(obj) = weak.upgrade() {
+                *value.borrow_mut() = read(&obj);
+            }
+        }
+        value.borrow().clone()
+    })
+}
+
+/// A ListBox row created from a list item.
+struct ListRow<V, W> {
+    item: V,
+    widget: W,
+    /// The row that contains `widget`, or `None` if it hasn't been inserted yet.
+    row: Option<gtk::ListBoxRow>,
+}
+
+type UpdateRowFn<W, V> = Box<dyn Fn(&W, &V)>;
+
+/// Rows of a ListBox bound to a list of items.
+struct ListRows<V, K, W> {
+    rows: Vec<ListRow<V, W>>,
+    /// Position of each row, used by the ListBox sort function.
+    order: Rc<RefCell<HashMap<gtk::ListBoxRow, usize>>>,
+    key_fn: Box<dyn Fn(&V) -> K>,
+    build_row: Box<dyn Fn(&V) -> W>,
+    update_row: UpdateRowFn<W, V>,
+}
+
+impl<V, K, W> ListRows<V, K, W>
+where
+    V: Clone + PartialEq,
+    K: Eq + Hash,
+    W: IsA<Widget>,
+{
+    /// Changes the rows to match the new items.
+    fn update(&mut self, listbox: &gtk::ListBox, items: &[V]) {
+        let mut old: HashMap<K, ListRow<V, W>> = HashMap::new();
+        for row in self.rows.drain(..) {
+            let key = (self.key_fn)(&row.item);
+            // rows with duplicate keys can't be matched, so they're replaced
+            if let Some(ListRow { row: Some(dup), .. }) = old.insert(key, row) {
+                listbox.remove(&dup);
+            }
+        }
+        // match the items with the existing rows
+        let mut rows = Vec::with_capacity(items.len());
+        for item in items {
+            let key = (self.key_fn)(item);
+            let row = match old.remove(&key) {
+                Some(mut row) => {
+                    if row.item != *item {
+                        (self.update_row)(&row.widget, item);
+                        row.item = item.clone();
+                    }
+                    row
+                }
+                None => ListRow {
+                    item: item.clone(),
+                    widget: (self.build_row)(item),
+                    row: None,
+                },
+            };
+            rows.push(row);
+        }
+        for (_, row) in old {
+            if let Some(row) = row.row {
+                listbox.remove(&row);
+            }
+        }
+        for row in &mut rows {
+            if row.row.is_none() {
+                listbox.insert(&row.widget, -1);
+                row.row = list_box_row(&row.widget);
+            }
+        }
+        // sorting moves the rows in place, so they keep their selection and focus
+        *self.order.borrow_mut() = rows
+            .iter()
+            .enumerate()
+            .filter_map(|(i, row)| Some((row.row.clone()?, i)))
+            .collect();
+        listbox.invalidate_sort();
+        self.rows = rows;
+    }
+}
+
+/// Returns the ListBoxRow that contains a widget inserted into a ListBox.
+fn list_box_row<W: IsA<Widget>>(widget: &W) -> Option<gtk::ListBoxRow> {
+    match widget.dynamic_cast_ref::<gtk::ListBoxRow>() {
+        Some(row) => Some(row.clone()),
+        None => widget.parent()?.downcast().ok(),
+    }
+}
+
+/// Checks if a property can be read as values of the specified type.
+fn check_readable_property<O>(
+    obj: &O,
+    name: &str,
+    value_type: glib::Type,
+) -> Result<(), PropertyError>
+where
+    O: ObjectExt,
+{
+    let pspec = obj
+        .find_property(name)
+        .ok_or_else(|| PropertyError::NotFound {
+            type_name: obj.type_().to_string(),
+            property: name.to_owned(),
+        })?;
+    if !pspec.flags().contains(glib::ParamFlags::READABLE) {
+        return Err(PropertyError::NotReadable {
+            type_name: obj.type_().to_string(),
+            property: name.to_owned(),
+        });
+    }
+    if !pspec.value_type().is_a(value_type) {
+        return Err(PropertyError::TypeMismatch {
+            property: name.to_owned(),
+            expected: pspec.value_type(),
+            found: value_type,
+        });
+    }
+    Ok(())
+}
+
+/// Checks if a property can be set with values of the specified type.
+fn check_writable_property<O>(
+    obj: &O,
+    name: &str,
+    value_type: glib::Type,
+) -> Result<(), PropertyError>
+where
+    O: ObjectExt,
+{
+    let pspec = obj
+        .find_property(name)
+        .ok_or_else(|| PropertyError::NotFound {
+            type_name: obj.type_().to_string(),
+            property: name.to_owned(),
+        })?;
+    let flags = pspec.flags();
+    if !flags.contains(glib::ParamFlags::WRITABLE)
+        || flags.contains(glib::ParamFlags::CONSTRUCT_ONLY)
+    {
+        return Err(PropertyError::NotWritable {
+            type_name: obj.type_().to_string(),
+            property: name.to_owned(),
+        });
+    }
+    if !value_type.is_a(pspec.value_type()) {
+        return Err(PropertyError::TypeMismatch {
+            property: name.to_owned(),
+            expected: pspec.value_type(),
+            found: value_type,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{collect, run_for, with_context};
+    use std::sync::atomic::{AtomicI32, AtomicUsize, Ordering};
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    /// Binds an object to a stream that holds `alive` while the stream chain exists.
+    fn bind_counter(
+        obj: &glib::Object,
+        sink: &Sink<i32>,
+        alive: &Arc<()>,
+    ) -> (Subscription, Arc<Mutex<Vec<i32>>>) {
+        let alive = alive.clone();
+        // the sink keeps it's direct callbacks until the next value, so `alive` goes one step after
+        let stream = sink.stream().filter(|_| true).filter(move |_| {
+            let _keepalive = &alive;
+            true
+        });
+        let values = Arc::new(Mutex::new(Vec::new()));
+        let values_ = values.clone();
+        let sub = bind_object(obj, &stream, move |_, n| values_.lock().unwrap().push(*n));
+        (sub, values)
+    }
+
+    #[test]
+    fn binding_released_on_drop() {
+        let obj = glib::Object::new::<glib::Object>(&[]).unwrap();
+        let sink = Sink::new();
+        let alive = Arc::new(());
+        let (sub, values) = bind_counter(&obj, &sink, &alive);
+        sink.send(1);
+        assert_eq!(Arc::strong_count(&alive), 2);
+        drop(sub);
+        // the stream chain is gone without waiting for another value
+        assert_eq!(Arc::strong_count(&alive), 1);
+        sink.send(2);
+        assert_eq!(*values.lock().unwrap(), [1]);
+    }
+
+    #[test]
+    fn detached_binding_lives_with_object() {
+        let obj = glib::Object::new::<glib::Object>(&[]).unwrap();
+        let sink = Sink::new();
+        let alive = Arc::new(());
+        let (sub, values) = bind_counter(&obj, &sink, &alive);
+        sub.detach();
+        sink.send(1);
+        assert_eq!(Arc::strong_count(&alive), 2);
+        drop(obj);
+        sink.send(2);
+        assert_eq!(Arc::strong_count(&alive), 1);
+        assert_eq!(*values.lock().unwrap(), [1]);
+    }
+
+    #[test]
+    fn signal_binding_polls_while_bound() {
+        with_context(|ctx| {
+            let obj = glib::Object::new::<glib::Object>(&[]).unwrap();
+            let value = Arc::new(AtomicI32::new(0));
+            let samples = Arc::new(AtomicUsize::new(0));
+            let (value_, samples_) = (value.clone(), samples.clone());
+            let signal = Signal::from_fn(move || {
+                samples_.fetch_add(1, Ordering::Relaxed);
+                value_.load(Ordering::Relaxed)
+            });
+            let sink = Sink::new();
+            let values = collect(&sink.stream());
+            let sub = bind_object(&obj, &signal, move |_, n| sink.send(*n));
+            value.store(5, Ordering::Relaxed);
+            run_for(ctx, Duration::from_millis(50));
+            assert_eq!(*values.lock().unwrap(), [0, 5]);
+            drop(sub);
+            let count = samples.load(Ordering::Relaxed);
+            run_for(ctx, Duration::from_millis(50));
+            assert_eq!(samples.load(Ordering::Relaxed), count);
+        })
+    }
+
+    #[test]
+    fn property_signal_reads_changes() {
+        let action = gtk::gio::SimpleAction::new("test", None);
+        let enabled = action.property_signal::<bool>("enabled").unwrap();
+        assert!(enabled.sample());
+        action.set_enabled(false);
+        assert!(!enabled.sample());
+        assert!(!enabled.sample());
+    }
+
+    #[test]
+    fn property_stream_checks_property() {
+        let action = gtk::gio::SimpleAction::new("test", None);
+        let stream = action.property_stream::<bool>("enabled").unwrap();
+        let changes = collect(&stream);
+        action.set_enabled(false);
+        assert_eq!(*changes.lock().unwrap(), [false]);
+
+        let err = action.property_stream::<bool>("missing").err();
+        assert!(matches!(err, Some(PropertyError::NotFound { .. })));
+        let err = action.property_stream::<String>("enabled").err();
+        assert!(matches!(err, Some(PropertyError::TypeMismatch { .. })));
+        let err = action.property_signal::<bool>("missing").err();
+        assert!(matches!(err, Some(PropertyError::NotFound { .. })));
+    }
+
+    #[test]
+    fn property_signal_unset_value() {
+        let action = gtk::gio::SimpleAction::new("test", None);
+        let err = action
+            .property_signal::<glib::VariantType>("parameter-type")
+            .err();
+        assert!(matches!(err, Some(PropertyError::Unset { .. })));
+        let param = action
+            .property_signal::<Option<glib::VariantType>>("parameter-type")
+            .unwrap();
+        assert_eq!(param.sample(), None);
+    }
 }
@@ -1,6 +1,12 @@
 #[macro_use]
 mod macros;
+pub mod channel;
 pub mod frappe_ext;
 pub mod gtk_ext;
+pub mod list_store;
 pub mod prelude;
+pub mod radio_group;
+#[cfg(test)]
+mod test_util;
+pub mod time;
 pub mod types;
@@ -0,0 +1,197 @@
+//! A `gtk::ListStore` driven by a stream of list changes.
+
+use crate::gtk_ext::bind_object;
+use crate::types::{ListOp, Subscription};
+use frappe::{Signal, Stream};
+use gtk::prelude::*;
+use std::sync::{Arc, Mutex};
+
+/// Maps list items into the value of a ListStore column.
+pub struct ListColumn<T> {
+    value_type: glib::Type,
+    get: Box<dyn Fn(&T) -> glib::Value>,
+}
+
+impl<T> ListColumn<T> {
+    /// Creates a column with the values returned by `f`.
+    pub fn new<V, F>(f: F) -> Self
+    where
+        V: ToValue + StaticType,
+        F: Fn(&T) -> V + 'static,
+    {
+        ListColumn {
+            value_type: V::static_type(),
+            get: Box::new(move |item| f(item).to_value()),
+        }
+    }
+}
+
+/// A `gtk::ListStore` that contains a list of items.
+///
+/// The list is changed by applying the `ListOp` values from a Stream, and the items are written
+/// into the store using the column mappings. The store stops receiving changes when this is
+/// dropped.
+pub struct ReactiveListStore<T> {
+    store: gtk::ListStore,
+    rows: Arc<Mutex<Vec<T>>>,
+    _sub: Subscription,
+}
+
+impl<T: Clone + Send + 'static> ReactiveListStore<T> {
+    /// Creates a new store with the specified columns, that applies the changes from `ops`.
+    pub fn new(columns: Vec<ListColumn<T>>, ops: &Stream<ListOp<T>>) -> Self {
+        let types: Vec<_> = columns.iter().map(|col| col.value_type).collect();
+        let store = gtk::ListStore::new(&types);
+        let rows = Arc::new(Mutex::new(Vec::new()));
+        let rows_ = rows.clone();
+        let sub = bind_object(&store, ops, move |store, op| {
+            let mut rows = rows_.lock().unwrap();
+            if !apply_op(&mut rows, op) {
+                glib::g_warning!(
+                    "frappe-gtk",
+                    "list operation out of bounds (length {})",
+                    rows.len()
+                );
+                return;
+            }
+            // the store signals are emitted right away, and their handlers may read the rows
+            drop(rows);
+            apply_store_op(store, &columns, op);
+        });
+        ReactiveListStore {
+            store,
+            rows,
+            _sub: sub,
+        }
+    }
+
+    /// Returns the underlying ListStore, to be used as a TreeView model.
+    pub fn store(&self) -> &gtk::ListStore {
+        &self.store
+    }
+
+    /// Returns a Signal with the current items.
+    pub fn rows(&self) -> Signal<Vec<T>> {
+        let rows = self.rows.clone();
+        Signal::from_fn(move || rows.lock().unwrap().clone())
+    }
+}
+
+/// Applies a list operation to a Vec.
+///
+/// Returns `false` if the operation was out of bounds.
+fn apply_op<T: Clone>(rows: &mut Vec<T>, op: &ListOp<T>) -> bool {
+    let len = rows.len();
+    match op {
+        ListOp::Insert(i, _) if *i > len => return false,
+        ListOp::Remove(i) | ListOp::Update(i, _) if *i >= len => return false,
+        ListOp::Move(from, to) if *from >= len || *to >= len => return false,
+        _ => (),
+    }
+    match op {
+        ListOp::Insert(i, item) => rows.insert(*i, item.clone()),
+        ListOp::Remove(i) => {
+            rows.remove(*i);
+        }
+        ListOp::Update(i, item) => rows[*i] = item.clone(),
+        ListOp::Move(from, to) => {
+            let item = rows.remove(*from);
+            rows.insert(*to, item);
+        }
+        ListOp::Reset(items) => *rows = items.clone(),
+    }
+    true
+}
+
+/// Applies a list operation to a ListStore. The operation must be in bounds.
+fn apply_store_op<T>(store: &gtk::ListStore, columns: &[ListColumn<T>], op: &ListOp<T>) {
+    let nth = |i: usize| store.iter_nth_child(None, i as i32);
+    let insert = |i: Option<u32>, item: &T| {
+        let values: Vec<_> = columns.iter().map(|col| (col.get)(item)).collect();
+        let columns: Vec<(u32, &dyn ToValue)> = values
+            .iter()
+            .enumerate()
+            .map(|(n, val)| (n as u32, val as &dyn ToValue))
+            .collect();
+        store.insert_with_values(i, &columns);
+    };
+    match op {
+        ListOp::Insert(i, item) => insert(Some(*i as u32), item),
+        ListOp::Remove(i) => {
+            if let Some(iter) = nth(*i) {
+                store.remove(&iter);
+            }
+        }
+        ListOp::Update(i, item) => {
+            if let Some(iter) = nth(*i) {
+                for (n, col) in columns.iter().enumerate() {
+                    store.set_value(&iter, n as u32, &(col.get)(item));
+                }
+            }
+        }
+        ListOp::Move(from, to) => {
+            if let (Some(iter), Some(pos)) = (nth(*from), nth(*to)) {
+                if from < to {
+                    store.move_after(&iter, Some(&pos));
+                } else if from > to {
+                    store.move_before(&iter, Some(&pos));
+                }
+            }
+        }
+        ListOp::Reset(items) => {
+            store.clear();
+            for item in items {
+                insert(None, item);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::on_gtk_thread;
+    use frappe::Sink;
+
+    #[test]
+    fn list_ops() {
+        let mut rows = vec![1, 2, 3];
+        assert!(apply_op(&mut rows, &ListOp::Insert(3, 4)));
+        assert!(apply_op(&mut rows, &ListOp::Remove(0)));
+        assert!(apply_op(&mut rows, &ListOp::Update(1, 5)));
+        assert_eq!(rows, [2, 5, 4]);
+        assert!(apply_op(&mut rows, &ListOp::Move(0, 2)));
+        assert_eq!(rows, [5, 4, 2]);
+        assert!(apply_op(&mut rows, &ListOp::Move(2, 0)));
+        assert_eq!(rows, [2, 5, 4]);
+        assert!(apply_op(&mut rows, &ListOp::Reset(vec![7])));
+        assert_eq!(rows, [7]);
+    }
+
+    #[test]
+    fn list_ops_out_of_bounds() {
+        let mut rows = vec![1, 2];
+        assert!(!apply_op(&mut rows, &ListOp::Insert(3, 0)));
+        assert!(!apply_op(&mut rows, &ListOp::Remove(2)));
+        assert!(!apply_op(&mut rows, &ListOp::Update(2, 0)));
+        assert!(!apply_op(&mut rows, &ListOp::Move(0, 2)));
+        assert_eq!(rows, [1, 2]);
+    }
+
+    #[test]
+    fn rows_from_store_signal() {
+        on_gtk_thread(|| {
+            let sink = Sink::new();
+            let list = ReactiveListStore::new(vec![ListColumn::new(|n: &i32| *n)], &sink.stream());
+            let rows = list.rows();
+            let seen = Arc::new(Mutex::new(Vec::new()));
+            let seen_ = seen.clone();
+            list.store().connect_row_inserted(move |_, _, _| {
+                seen_.lock().unwrap().push(rows.sample());
+            });
+            sink.send(ListOp::Insert(0, 1));
+            sink.send(ListOp::Insert(1, 2));
+            assert_eq!(*seen.lock().unwrap(), [vec![1], vec![1, 2]]);
+        })
+    }
+}
@@ -7,18 +7,24 @@ macro_rules! gtk_lift {
     }};
 }
 
-/// Calls the specified Gtk method using the values from a Stream.
+/// Calls the specified Gtk method using the values from a Stream or Signal.
+///
+/// Returns a `Subscription` that removes the binding when dropped. The binding is also removed
+/// when the object is destroyed.
 #[macro_export]
 macro_rules! gtk_observe {
     ($stream:expr , | $($args:pat),+ | $obj:ident . $method:ident ( $($e:expr),+ )) => ({
-        let weak = $crate::types::Fragile::new($obj.downgrade());
-        $stream.observe_strong(move |$($args),+| {
-            weak.get().upgrade().map(|obj| obj.$method($($e),+));
-        });
+        $crate::gtk_ext::bind_weak($obj.downgrade(), $stream, |this, $($args),+| {
+            this.$method($($e),+);
+        })
     });
 }
 
-/// Sends the specified object's Gtk events into a Stream.
+/// Sends the specified object's Gtk events into an `EventStream`.
+///
+/// The object can be passed by value or by reference. The signal handler is disconnected when the
+/// resulting stream is dropped. With the `=> f` form, the handler returns `Inhibit(f(&value))`,
+/// decided before the value is sent.
 #[macro_export]
 macro_rules! connect_stream {
     ($obj:ident . $method:ident) => ({
@@ -28,7 +34,20 @@ macro_rules! connect_stream {
     ($obj:ident . $method:ident , | $($args:pat),+ | $e:expr $(;$ret:expr)?) => ({
         let sink = frappe::Sink::new();
         let stream = sink.stream();
-        $obj.$method(move |$($args),+| { sink.send($e) $(;$ret)? });
-        stream
+        let id = $obj.$method(move |$($args),+| { sink.send($e) $(;$ret)? });
+        $crate::types::EventStream::from_weak(stream, $obj.downgrade(), id)
+    });
+
+    ($obj:ident . $method:ident , | $($args:pat),+ | $e:expr => $inhibit:expr) => ({
+        let sink = frappe::Sink::new();
+        let stream = sink.stream();
+        let inhibit = $inhibit;
+        let id = $obj.$method(move |$($args),+| {
+            let val = $e;
+            let ret = gtk::Inhibit(inhibit(&val));
+            sink.send(val);
+            ret
+        });
+        $crate::types::EventStream::from_weak(stream, $obj.downgrade(), id)
     });
 }
@@ -0,0 +1,123 @@
+//! Mapping a group of radio buttons onto a value.
+
+use crate::gtk_ext::bind_object;
+use crate::types::{Disconnector, IntoBinding, Subscription};
+use frappe::{Signal, Sink, Stream};
+use gtk::prelude::*;
+use gtk::Widget;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Widgets that can be items of a `RadioGroup`.
+///
+/// This is implemented for `gtk::RadioButton` and `gtk::RadioMenuItem`.
+pub trait RadioItem: IsA<Widget> + private::Sealed {}
+
+impl RadioItem for gtk::RadioButton {}
+impl RadioItem for gtk::RadioMenuItem {}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for gtk::RadioButton {}
+    impl Sealed for gtk::RadioMenuItem {}
+}
+
+/// Error returned when creating a `RadioGroup` without items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmptyGroupError;
+
+impl fmt::Display for EmptyGroupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a RadioGroup needs at least one item")
+    }
+}
+
+impl Error for EmptyGroupError {}
+
+/// A group of `RadioButton`s or `RadioMenuItem`s that selects one of a set of values.
+///
+/// Each item is associated with a value, and the value of the active item is the value of the
+/// group. The group stops tracking the items when this is dropped.
+pub struct RadioGroup<E> {
+    items: Vec<(Widget, E)>,
+    current: Arc<Mutex<E>>,
+    stream: Stream<E>,
+    /// Object that lives as long as the group, for tying bindings to it.
+    anchor: glib::Object,
+    _handlers: Vec<Disconnector>,
+}
+
+impl<E> RadioGroup<E>
+where
+    E: Clone + PartialEq + Send + Sync + 'static,
+{
+    /// Creates a group from items and their values.
+    ///
+    /// Fails if `items` is empty.
+    pub fn new<W: RadioItem>(items: Vec<(W, E)>) -> Result<Self, EmptyGroupError> {
+        let items: Vec<(Widget, E)> = items
+            .into_iter()
+            .map(|(w, val)| (w.upcast(), val))
+            .collect();
+        let initial = items
+            .iter()
+            .find(|(w, _)| w.property::<bool>("active"))
+            .or_else(|| items.first())
+            .map(|(_, val)| val.clone())
+            .ok_or(EmptyGroupError)?;
+        let current = Arc::new(Mutex::new(initial));
+        let sink = Sink::new();
+        let stream = sink.stream();
+        let handlers = items
+            .iter()
+            .map(|(w, val)| {
+                let (current, sink, val) = (current.clone(), sink.clone(), val.clone());
+                let id = w.connect_notify_local(Some("active"), move |w, _| {
+                    if w.property::<bool>("active") {
+                        *current.lock().unwrap() = val.clone();
+                        sink.send(val.clone());
+                    }
+                });
+                Disconnector::signal(w, id)
+            })
+            .collect();
+        Ok(RadioGroup {
+            items,
+            current,
+            stream,
+            anchor: glib::Object::new(&[]).unwrap(),
+            _handlers: handlers,
+        })
+    }
+
+    /// Returns a Signal with the value of the active item.
+    pub fn selection_signal(&self) -> Signal<E> {
+        let current = self.current.clone();
+        Signal::from_fn(move || current.lock().unwrap().clone())
+    }
+
+    /// Returns a Stream with the value of the active item every time it changes.
+    pub fn selected_events(&self) -> Stream<E> {
+        self.stream.clone()
+    }
+
+    /// Activates the item with the values from a Stream or Signal.
+    ///
+    /// Values that don't belong to any item are ignored. The binding is tied to the group, so a
+    /// detached binding only lasts as long as the group does.
+    pub fn stream_selected(&self, stream: impl IntoBinding<E>) -> Subscription {
+        let items: Vec<_> = self
+            .items
+            .iter()
+            .map(|(w, val)| (w.downgrade(), val.clone()))
+            .collect();
+        bind_object(&self.anchor, stream, move |_, val| {
+            let item = items.iter().find(|(_, v)| v == val);
+            if let Some(w) = item.and_then(|(weak, _)| weak.upgrade()) {
+                w.set_property("active", true);
+            }
+        })
+    }
+}
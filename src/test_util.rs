@@ -0,0 +1,62 @@
+//! Helpers for tests that run a main context.
+
+use frappe::Stream;
+use glib::MainContext;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+type Job = Box<dyn FnOnce() + Send>;
+type JobSender = mpsc::Sender<(Job, mpsc::Sender<thread::Result<()>>)>;
+
+/// Runs `f` on a thread that is marked as the Gtk main thread.
+///
+/// Gtk objects that don't need a display, like models, can be created there without calling
+/// `gtk::init`. The tests that use this run one at a time.
+pub fn on_gtk_thread<F: FnOnce() + Send + 'static>(f: F) {
+    static JOBS: OnceLock<Mutex<JobSender>> = OnceLock::new();
+    let jobs = JOBS.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<(Job, mpsc::Sender<thread::Result<()>>)>();
+        thread::spawn(move || {
+            unsafe { gtk::set_initialized() };
+            for (job, done) in receiver {
+                let _ = done.send(panic::catch_unwind(AssertUnwindSafe(job)));
+            }
+        });
+        Mutex::new(sender)
+    });
+    let (done, result) = mpsc::channel();
+    jobs.lock().unwrap().send((Box::new(f), done)).unwrap();
+    if let Err(e) = result.recv().unwrap() {
+        panic::resume_unwind(e)
+    }
+}
+
+/// Runs the test on it's own main context, so the timers don't interfere with other tests.
+pub fn with_context<F: FnOnce(&MainContext)>(f: F) {
+    let ctx = MainContext::new();
+    ctx.with_thread_default(|| f(&ctx)).unwrap();
+}
+
+/// Dispatches the main context events for the specified time.
+pub fn run_for(ctx: &MainContext, time: Duration) {
+    run_until(ctx, time, || false)
+}
+
+/// Dispatches the main context events until `done` returns `true` or `timeout` has passed.
+pub fn run_until(ctx: &MainContext, timeout: Duration, mut done: impl FnMut() -> bool) {
+    let start = Instant::now();
+    while !done() && start.elapsed() < timeout {
+        while ctx.iteration(false) {}
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+
+/// Collects the values sent to a Stream.
+pub fn collect<T: Clone + Send + 'static>(stream: &Stream<T>) -> Arc<Mutex<Vec<T>>> {
+    let values = Arc::new(Mutex::new(Vec::new()));
+    let values_ = values.clone();
+    stream.observe(move |val| values_.lock().unwrap().push(val.into_owned()));
+    values
+}
@@ -0,0 +1,83 @@
+//! Timer streams driven by the Gtk main loop.
+//!
+//! The timers are attached to the thread-default main context of the calling thread, which is the
+//! main thread's context unless another one was pushed. They stop when the returned Stream is
+//! dropped.
+
+use crate::frappe_ext::attach_timeout;
+use crate::types::{Disconnector, EventStream};
+use frappe::Sink;
+use glib::{Continue, MainContext};
+use std::time::Duration;
+
+/// Returns a Stream that fires periodically with the specified interval.
+pub fn interval(period: Duration) -> EventStream<()> {
+    let sink = Sink::new();
+    let stream = sink.stream();
+    let source = attach_timeout(&MainContext::ref_thread_default(), period, move || {
+        sink.send(());
+        Continue(true)
+    });
+    EventStream::from_disconnector(stream, Disconnector::new(move || source.destroy()))
+}
+
+/// Returns a Stream that fires once after the specified delay.
+pub fn timeout(delay: Duration) -> EventStream<()> {
+    let sink = Sink::new();
+    let stream = sink.stream();
+    let source = attach_timeout(&MainContext::ref_thread_default(), delay, move || {
+        sink.send(());
+        Continue(false)
+    });
+    // destroying the source again after it fired does nothing
+    EventStream::from_disconnector(stream, Disconnector::new(move || source.destroy()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{collect, run_for, run_until, with_context};
+    use std::time::Instant;
+
+    const PERIOD: Duration = Duration::from_millis(20);
+
+    #[test]
+    fn interval_fires_until_dropped() {
+        with_context(|ctx| {
+            let ticks = interval(PERIOD);
+            let values = collect(&ticks);
+            run_until(ctx, PERIOD * 50, || values.lock().unwrap().len() >= 3);
+            assert!(values.lock().unwrap().len() >= 3);
+
+            drop(ticks);
+            let count = values.lock().unwrap().len();
+            run_for(ctx, PERIOD * 3);
+            assert_eq!(values.lock().unwrap().len(), count);
+        })
+    }
+
+    #[test]
+    fn timeout_fires_once() {
+        with_context(|ctx| {
+            let start = Instant::now();
+            let fired = timeout(PERIOD);
+            let values = collect(&fired);
+            run_until(ctx, PERIOD * 50, || !values.lock().unwrap().is_empty());
+            assert!(start.elapsed() >= PERIOD);
+
+            run_for(ctx, PERIOD * 3);
+            assert_eq!(values.lock().unwrap().len(), 1);
+        })
+    }
+
+    #[test]
+    fn timeout_cancelled_on_drop() {
+        with_context(|ctx| {
+            let fired = timeout(PERIOD);
+            let values = collect(&fired);
+            drop(fired);
+            run_for(ctx, PERIOD * 3);
+            assert!(values.lock().unwrap().is_empty());
+        })
+    }
+}
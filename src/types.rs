@@ -1,7 +1,16 @@
+use crate::frappe_ext::poll_signal;
 pub use fragile::Fragile;
+use frappe::{Signal, Sink, Stream};
+use glib::prelude::*;
+use glib::{SignalHandlerId, WeakRef};
+use std::error::Error;
+use std::fmt;
+use std::ops::Deref;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 
 /// Box packing arguments.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct PackArgs<W> {
     pub child: W,
     pub expand: bool,
@@ -17,7 +26,7 @@ pub struct ToggleState {
 }
 
 /// Widget and position arguments for `gtk::Grid`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct GridArgs<W> {
     pub child: W,
     pub left: i32,
@@ -26,6 +35,197 @@ pub struct GridArgs<W> {
     pub height: i32,
 }
 
+/// Text inserted into a `gtk::Editable`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextInsert {
+    pub text: String,
+    /// Character position where the text was inserted.
+    pub position: i32,
+}
+
+/// Character range deleted from a `gtk::Editable`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextDelete {
+    pub start: i32,
+    pub end: i32,
+}
+
+/// Data of a key press or release event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyEvent {
+    pub keyval: gdk::keys::Key,
+    pub modifiers: gdk::ModifierType,
+    pub hardware_keycode: u16,
+    pub time: u32,
+}
+
+impl From<&gdk::EventKey> for KeyEvent {
+    fn from(ev: &gdk::EventKey) -> Self {
+        KeyEvent {
+            keyval: ev.keyval(),
+            modifiers: ev.state(),
+            hardware_keycode: ev.hardware_keycode(),
+            time: ev.time(),
+        }
+    }
+}
+
+/// Data of a pointer motion event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointerEvent {
+    /// Pointer position relative to the widget's window.
+    pub x: f64,
+    pub y: f64,
+    /// Pointer position relative to the root window.
+    pub x_root: f64,
+    pub y_root: f64,
+    pub modifiers: gdk::ModifierType,
+    pub time: u32,
+}
+
+impl From<&gdk::EventMotion> for PointerEvent {
+    fn from(ev: &gdk::EventMotion) -> Self {
+        let (x, y) = ev.position();
+        let (x_root, y_root) = ev.root();
+        PointerEvent {
+            x,
+            y,
+            x_root,
+            y_root,
+            modifiers: ev.state(),
+            time: ev.time(),
+        }
+    }
+}
+
+/// Data of a pointer enter or leave event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CrossingEvent {
+    /// Pointer position relative to the widget's window.
+    pub x: f64,
+    pub y: f64,
+    /// Pointer position relative to the root window.
+    pub x_root: f64,
+    pub y_root: f64,
+    pub mode: gdk::CrossingMode,
+    pub detail: gdk::NotifyType,
+    /// If the window is or contains the focus window.
+    pub focus: bool,
+    pub modifiers: gdk::ModifierType,
+    pub time: u32,
+}
+
+impl From<&gdk::EventCrossing> for CrossingEvent {
+    fn from(ev: &gdk::EventCrossing) -> Self {
+        let (x, y) = ev.position();
+        let (x_root, y_root) = ev.root();
+        CrossingEvent {
+            x,
+            y,
+            x_root,
+            y_root,
+            mode: ev.mode(),
+            detail: ev.detail(),
+            focus: ev.gets_focus(),
+            modifiers: ev.state(),
+            time: ev.time(),
+        }
+    }
+}
+
+/// Data of a mouse button press or release event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ButtonEvent {
+    pub button: u32,
+    /// Pointer position relative to the widget's window.
+    pub x: f64,
+    pub y: f64,
+    pub modifiers: gdk::ModifierType,
+    /// `DoubleButtonPress` and `TripleButtonPress` are sent after the second and third clicks.
+    pub event_type: gdk::EventType,
+    pub time: u32,
+}
+
+impl From<&gdk::EventButton> for ButtonEvent {
+    fn from(ev: &gdk::EventButton) -> Self {
+        let (x, y) = ev.position();
+        ButtonEvent {
+            button: ev.button(),
+            x,
+            y,
+            modifiers: ev.state(),
+            event_type: ev.event_type(),
+            time: ev.time(),
+        }
+    }
+}
+
+/// Data of a scroll event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScrollEvent {
+    /// `Smooth` if the deltas contain the scroll amount.
+    pub direction: gdk::ScrollDirection,
+    pub delta_x: f64,
+    pub delta_y: f64,
+    /// Pointer position relative to the widget's window.
+    pub x: f64,
+    pub y: f64,
+    pub modifiers: gdk::ModifierType,
+    pub time: u32,
+}
+
+impl From<&gdk::EventScroll> for ScrollEvent {
+    fn from(ev: &gdk::EventScroll) -> Self {
+        let (delta_x, delta_y) = ev.delta();
+        let (x, y) = ev.position();
+        ScrollEvent {
+            direction: ev.direction(),
+            delta_x,
+            delta_y,
+            x,
+            y,
+            modifiers: ev.state(),
+            time: ev.time(),
+        }
+    }
+}
+
+/// Clicks recognized by a `gtk::GestureMultiPress`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MultiPressEvent {
+    /// Number of consecutive clicks.
+    pub n_press: i32,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Stage of a drag gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DragPhase {
+    Begin,
+    Update,
+    End,
+}
+
+/// Drag recognized by a `gtk::GestureDrag`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DragEvent {
+    pub phase: DragPhase,
+    /// Point where the drag started.
+    pub start_x: f64,
+    pub start_y: f64,
+    /// Offset from the start point.
+    pub offset_x: f64,
+    pub offset_y: f64,
+}
+
+/// Long press recognized by a `gtk::GestureLongPress`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LongPressEvent {
+    pub x: f64,
+    pub y: f64,
+}
+
 /// Notebook child widget and page number.
 #[derive(Debug, Clone)]
 pub struct NotebookPage {
@@ -33,9 +233,557 @@ pub struct NotebookPage {
     pub page_num: u32,
 }
 
+/// Limits and step of a `gtk::Adjustment`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValueBounds {
+    pub lower: f64,
+    pub upper: f64,
+    pub step: f64,
+}
+
+/// Row activated in a `gtk::TreeView`.
+#[derive(Debug, Clone)]
+pub struct TreeRowActivated {
+    pub path: gtk::TreePath,
+    pub column: gtk::TreeViewColumn,
+}
+
 /// Value of a `gtk::Range`.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct RangeValue {
     pub scroll: gtk::ScrollType,
     pub value: f64,
 }
+
+/// What to do with a dialog after it receives a response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DialogClose {
+    /// Leave the dialog visible.
+    Keep,
+    /// Hide the dialog so it can be shown again.
+    Hide,
+    /// Destroy the dialog.
+    Destroy,
+}
+
+/// Options for the file chooser dialogs created by `StreamExt::choose_file`.
+#[derive(Debug, Clone)]
+pub struct FileChooserSpec {
+    pub title: Option<String>,
+    /// Window the dialog is transient for.
+    pub parent: Option<Fragile<gtk::Window>>,
+    pub action: gtk::FileChooserAction,
+    pub filters: Vec<FileFilterSpec>,
+    pub current_folder: Option<PathBuf>,
+    /// Suggested file name for `Save` and `CreateFolder` dialogs. Other actions ignore it.
+    pub current_name: Option<String>,
+    /// Asks for confirmation before choosing an existing file in save dialogs.
+    pub confirm_overwrite: bool,
+}
+
+impl Default for FileChooserSpec {
+    fn default() -> Self {
+        FileChooserSpec {
+            title: None,
+            parent: None,
+            action: gtk::FileChooserAction::Open,
+            filters: Vec::new(),
+            current_folder: None,
+            current_name: None,
+            confirm_overwrite: true,
+        }
+    }
+}
+
+/// A named file filter that matches by MIME type or glob pattern.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileFilterSpec {
+    pub name: String,
+    pub mime_types: Vec<String>,
+    pub patterns: Vec<String>,
+}
+
+/// Options for the message dialogs created by `StreamExt::confirm`.
+#[derive(Debug, Clone)]
+pub struct ConfirmSpec {
+    /// Window the dialog is transient for.
+    pub parent: Option<Fragile<gtk::Window>>,
+    pub message_type: gtk::MessageType,
+    pub buttons: gtk::ButtonsType,
+    pub text: String,
+    pub secondary_text: Option<String>,
+}
+
+impl Default for ConfirmSpec {
+    fn default() -> Self {
+        ConfirmSpec {
+            parent: None,
+            message_type: gtk::MessageType::Question,
+            buttons: gtk::ButtonsType::YesNo,
+            text: String::new(),
+            secondary_text: None,
+        }
+    }
+}
+
+/// Options for the message dialogs created by `StreamExt::ask_choice`.
+///
+/// Each choice is shown as a button with the specified label.
+#[derive(Debug, Clone)]
+pub struct ChoiceSpec<E> {
+    /// Window the dialog is transient for.
+    pub parent: Option<Fragile<gtk::Window>>,
+    pub message_type: gtk::MessageType,
+    pub text: String,
+    pub secondary_text: Option<String>,
+    pub choices: Vec<(String, E)>,
+}
+
+impl<E> Default for ChoiceSpec<E> {
+    fn default() -> Self {
+        ChoiceSpec {
+            parent: None,
+            message_type: gtk::MessageType::Question,
+            text: String::new(),
+            secondary_text: None,
+            choices: Vec::new(),
+        }
+    }
+}
+
+/// A change to a list of items.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ListOp<T> {
+    /// Inserts an item at the specified position.
+    Insert(usize, T),
+    /// Removes the item at the specified position.
+    Remove(usize),
+    /// Replaces the item at the specified position.
+    Update(usize, T),
+    /// Moves the item at the first position to the second position.
+    Move(usize, usize),
+    /// Replaces all the items.
+    Reset(Vec<T>),
+}
+
+type CleanupFn = Fragile<Box<dyn FnOnce()>>;
+
+/// Cleanup action that runs once on the main thread.
+pub(crate) struct Disconnector(Mutex<Option<CleanupFn>>);
+
+impl Disconnector {
+    /// Creates a disconnector that runs the specified cleanup action.
+    pub(crate) fn new<F: FnOnce() + 'static>(f: F) -> Self {
+        Disconnector(Mutex::new(Some(Fragile::new(Box::new(f)))))
+    }
+
+    /// Creates a disconnector for a signal handler.
+    pub(crate) fn signal<O: ObjectType>(obj: &O, id: SignalHandlerId) -> Self {
+        Self::signal_weak(obj.downgrade(), id)
+    }
+
+    /// Creates a disconnector for a signal handler, from a weak reference to the object.
+    pub(crate) fn signal_weak<O: ObjectType>(weak: WeakRef<O>, id: SignalHandlerId) -> Self {
+        Self::new(move || {
+            if let Some(obj) = weak.upgrade() {
+                // a disposed object (like a destroyed widget) already dropped it's handlers
+                let connected = unsafe {
+                    glib::gobject_ffi::g_signal_handler_is_connected(
+                        obj.as_ptr() as *mut glib::gobject_ffi::GObject,
+                        id.as_raw(),
+                    ) != 0
+                };
+                if connected {
+                    obj.disconnect(id)
+                }
+            }
+        })
+    }
+
+    /// Runs the cleanup action if it hasn't been run yet.
+    pub(crate) fn disconnect(&self) {
+        let action = self.0.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if let Some(f) = action {
+            if f.is_valid() {
+                (f.into_inner())()
+            } else {
+                // we can't touch Gtk objects from here, so send the action back to the main thread
+                glib::MainContext::default().invoke(move || (f.into_inner())());
+            }
+        }
+    }
+
+    /// Discards the cleanup action without running it.
+    pub(crate) fn forget(&self) {
+        let action = self.0.lock().unwrap_or_else(|e| e.into_inner()).take();
+        if let Some(f) = action {
+            if !f.is_valid() {
+                glib::MainContext::default().invoke(move || drop(f));
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Disconnector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Disconnector")
+    }
+}
+
+impl Drop for Disconnector {
+    fn drop(&mut self) {
+        self.disconnect()
+    }
+}
+
+/// A Stream of events that owns the Gtk signal handler feeding it.
+///
+/// The handler is disconnected when this stream and all the streams and observers derived from it
+/// are dropped, or when `EventStream::disconnect` is called.
+pub struct EventStream<T> {
+    stream: Stream<T>,
+    handler: Arc<Disconnector>,
+}
+
+impl<T: 'static> EventStream<T> {
+    /// Creates an event stream from a signal handler connected to `obj`.
+    ///
+    /// The handler should be the one that sends values into `stream`.
+    pub fn new<O: ObjectType>(stream: Stream<T>, obj: &O, id: SignalHandlerId) -> Self {
+        Self::from_weak(stream, obj.downgrade(), id)
+    }
+
+    /// Same as `EventStream::new`, but takes a weak reference to the object.
+    pub fn from_weak<O: ObjectType>(
+        stream: Stream<T>,
+        obj: WeakRef<O>,
+        id: SignalHandlerId,
+    ) -> Self {
+        Self::from_disconnector(stream, Disconnector::signal_weak(obj, id))
+    }
+
+    pub(crate) fn from_disconnector(stream: Stream<T>, handler: Disconnector) -> Self {
+        let handler = Arc::new(handler);
+        // the observer lives as long as the stream callbacks, so it ties the handler lifetime to
+        // every stream that references this one
+        let stream = stream.filter(|_| true);
+        let keepalive = handler.clone();
+        stream.observe(move |_| {
+            let _keepalive = &keepalive;
+        });
+        EventStream { stream, handler }
+    }
+
+    /// Disconnects the signal handler, so this stream won't receive any more events.
+    pub fn disconnect(&self) {
+        self.handler.disconnect()
+    }
+
+    /// Returns the underlying Stream.
+    pub fn into_stream(self) -> Stream<T> {
+        self.stream
+    }
+}
+
+impl<T> Deref for EventStream<T> {
+    type Target = Stream<T>;
+
+    fn deref(&self) -> &Stream<T> {
+        &self.stream
+    }
+}
+
+impl<T> Clone for EventStream<T> {
+    fn clone(&self) -> Self {
+        EventStream {
+            stream: self.stream.clone(),
+            handler: self.handler.clone(),
+        }
+    }
+}
+
+impl<T> From<EventStream<T>> for Stream<T> {
+    fn from(ev: EventStream<T>) -> Self {
+        ev.stream
+    }
+}
+
+/// Handle to a binding that sends Stream values into a Gtk object.
+///
+/// The binding is removed when this handle is dropped or when the widget is destroyed.
+/// Use `Subscription::detach` to keep it for as long as the object lives. Detached bindings of
+/// objects that aren't widgets are removed with the first value received after the object is gone.
+#[must_use = "the binding is removed when the Subscription is dropped"]
+#[derive(Debug)]
+pub struct Subscription {
+    handlers: Vec<Disconnector>,
+}
+
+impl Subscription {
+    /// Creates a subscription without handlers.
+    pub(crate) fn new() -> Self {
+        Subscription {
+            handlers: Vec::new(),
+        }
+    }
+
+    /// Adds a handler that will be disconnected when the binding is removed.
+    pub(crate) fn add_handler(&mut self, handler: Disconnector) {
+        self.handlers.push(handler)
+    }
+
+    /// Removes the binding.
+    pub fn unsubscribe(self) {}
+
+    /// Releases this handle without removing the binding.
+    pub fn detach(mut self) {
+        for handler in self.handlers.drain(..) {
+            handler.forget()
+        }
+    }
+}
+
+/// Error returned when a property can't be bound.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PropertyError {
+    /// The object doesn't have a property with this name.
+    NotFound { type_name: String, property: String },
+    /// The property is write-only.
+    NotReadable { type_name: String, property: String },
+    /// The property is read-only or can only be set at construction time.
+    NotWritable { type_name: String, property: String },
+    /// The property has no value, and it's being read as a type that isn't an `Option`.
+    Unset { type_name: String, property: String },
+    /// The value type is not compatible with the property type.
+    TypeMismatch {
+        property: String,
+        expected: glib::Type,
+        found: glib::Type,
+    },
+}
+
+impl fmt::Display for PropertyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyError::NotFound {
+                type_name,
+                property,
+            } => write!(f, "{} has no property named `{}`", type_name, property),
+            PropertyError::NotReadable {
+                type_name,
+                property,
+            } => write!(
+                f,
+                "property `{}` of {} is not readable",
+                property, type_name
+            ),
+            PropertyError::NotWritable {
+                type_name,
+                property,
+            } => write!(
+                f,
+                "property `{}` of {} is not writable",
+                property, type_name
+            ),
+            PropertyError::Unset {
+                type_name,
+                property,
+            } => write!(f, "property `{}` of {} has no value", property, type_name),
+            PropertyError::TypeMismatch {
+                property,
+                expected,
+                found,
+            } => write!(
+                f,
+                "property `{}` has type {}, but the value has type {}",
+                property, expected, found
+            ),
+        }
+    }
+}
+
+impl Error for PropertyError {}
+
+/// Error returned when an accelerator string can't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AcceleratorError(pub String);
+
+impl fmt::Display for AcceleratorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid accelerator: `{}`", self.0)
+    }
+}
+
+impl Error for AcceleratorError {}
+
+/// Values that can be sent into a Gtk object setter.
+///
+/// This is implemented for Streams and Signals. Signals apply their current value right away and
+/// then a new value every time it changes, sampled as described in `SignalExt::changes`.
+pub trait IntoBinding<T> {
+    fn into_binding(self) -> Binding<T>;
+}
+
+impl<T: 'static> IntoBinding<T> for &Stream<T> {
+    fn into_binding(self) -> Binding<T> {
+        Binding {
+            stream: self.clone(),
+            initial: None,
+            poller: None,
+        }
+    }
+}
+
+impl<T: 'static> IntoBinding<T> for &EventStream<T> {
+    fn into_binding(self) -> Binding<T> {
+        IntoBinding::into_binding(&**self)
+    }
+}
+
+impl<T> IntoBinding<T> for &Signal<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    fn into_binding(self) -> Binding<T> {
+        let sink = Sink::new();
+        let changes =
+            EventStream::from_disconnector(sink.stream(), poll_signal(self, sink.clone()));
+        Binding {
+            stream: changes.stream,
+            initial: Some((sink, self.sample())),
+            poller: Some(changes.handler),
+        }
+    }
+}
+
+/// Source of values for a Gtk object setter.
+pub struct Binding<T> {
+    stream: Stream<T>,
+    initial: Option<(Sink<T>, T)>,
+    /// Source that samples the Signal, if the values come from one.
+    poller: Option<Arc<Disconnector>>,
+}
+
+impl<T> Binding<T> {
+    /// Returns the Stream of values to apply.
+    pub fn stream(&self) -> &Stream<T> {
+        &self.stream
+    }
+
+    /// Takes the source that samples the Signal, so it can be stopped together with the binding.
+    ///
+    /// Otherwise it stops when the Stream is dropped.
+    pub(crate) fn take_poller(&mut self) -> Option<Arc<Disconnector>> {
+        self.poller.take()
+    }
+
+    /// Sends the initial value, if any.
+    ///
+    /// This must be called after observing the Stream.
+    pub fn start(self) {
+        if let Some((sink, val)) = self.initial {
+            sink.send(val)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::on_gtk_thread;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+    #[test]
+    fn event_data_is_send() {
+        assert_send_sync::<PointerEvent>();
+        assert_send_sync::<CrossingEvent>();
+        assert_send_sync::<KeyEvent>();
+        assert_send_sync::<ScrollEvent>();
+        assert_send_sync::<ButtonEvent>();
+    }
+
+    #[test]
+    fn pointer_event_from_motion() {
+        on_gtk_thread(|| {
+            let mut ev = gdk::Event::new(gdk::EventType::MotionNotify)
+                .downcast::<gdk::EventMotion>()
+                .unwrap();
+            let raw: &mut gdk::ffi::GdkEventMotion = ev.as_mut();
+            raw.x = 1.0;
+            raw.y = 2.0;
+            raw.x_root = 3.0;
+            raw.y_root = 4.0;
+            raw.state = gdk::ffi::GDK_SHIFT_MASK;
+            raw.time = 42;
+            assert_eq!(
+                PointerEvent::from(&ev),
+                PointerEvent {
+                    x: 1.0,
+                    y: 2.0,
+                    x_root: 3.0,
+                    y_root: 4.0,
+                    modifiers: gdk::ModifierType::SHIFT_MASK,
+                    time: 42,
+                }
+            );
+        })
+    }
+
+    #[test]
+    fn crossing_event_from_crossing() {
+        on_gtk_thread(|| {
+            let mut ev = gdk::Event::new(gdk::EventType::EnterNotify)
+                .downcast::<gdk::EventCrossing>()
+                .unwrap();
+            let raw: &mut gdk::ffi::GdkEventCrossing = ev.as_mut();
+            raw.x = 1.0;
+            raw.y = 2.0;
+            raw.x_root = 3.0;
+            raw.y_root = 4.0;
+            raw.mode = gdk::ffi::GDK_CROSSING_GRAB;
+            raw.detail = gdk::ffi::GDK_NOTIFY_INFERIOR;
+            raw.focus = glib::ffi::GTRUE;
+            raw.state = gdk::ffi::GDK_CONTROL_MASK;
+            raw.time = 42;
+            assert_eq!(
+                CrossingEvent::from(&ev),
+                CrossingEvent {
+                    x: 1.0,
+                    y: 2.0,
+                    x_root: 3.0,
+                    y_root: 4.0,
+                    mode: gdk::CrossingMode::Grab,
+                    detail: gdk::NotifyType::Inferior,
+                    focus: true,
+                    modifiers: gdk::ModifierType::CONTROL_MASK,
+                    time: 42,
+                }
+            );
+        })
+    }
+
+    #[test]
+    fn disconnect_after_dispose() {
+        let warnings = Arc::new(AtomicUsize::new(0));
+        let warnings_ = warnings.clone();
+        let log_id = glib::log_set_handler(
+            Some("GLib-GObject"),
+            glib::LogLevels::LEVEL_CRITICAL | glib::LogLevels::LEVEL_WARNING,
+            false,
+            false,
+            move |_, _, _| {
+                warnings_.fetch_add(1, Ordering::Relaxed);
+            },
+        );
+        let obj = glib::Object::new::<glib::Object>(&[]).unwrap();
+        let id = obj.connect_notify_local(None, |_, _| ());
+        let handler = Disconnector::signal(&obj, id);
+        // drops the handlers, like destroying a widget does
+        unsafe { glib::gobject_ffi::g_signal_handlers_destroy(obj.as_ptr()) };
+        handler.disconnect();
+        glib::log_remove_handler(Some("GLib-GObject"), log_id);
+        assert_eq!(warnings.load(Ordering::Relaxed), 0);
+    }
+}